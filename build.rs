@@ -1,117 +1,273 @@
 use std::{
-    ffi::OsString,
+    collections::{BTreeMap, HashSet},
+    env,
     fs::{self, File},
     io::{self, Write},
+    path::{Path, PathBuf},
 };
 
-use glob;
+const HEADER: &str = "// Generated by build.rs\n";
 
-macro_rules! write_to_file {
-    ($file:expr, $($arg:tt)*) => {
-        if let Err(e) = writeln!($file.as_ref().unwrap(), $($arg)*) {
-            println!("cargo:warning=Failed writing \"{}\" to file: \"{:?}\": {:?}", format!($($arg)*), $file, e);
+struct Day {
+    name: String,
+    /// The absolute path of the module, as `OUT_DIR` is elsewhere
+    file: PathBuf,
+    /// The verified answers from `answers/<yyyy>/<dd>.toml`, if the file exists
+    answers: Option<Answers>,
+}
+
+struct Answers {
+    part1: Option<String>,
+    part2: Option<String>,
+    /// Whether the input for the day was present when the build script ran. The test is ignored
+    /// if not
+    has_input: bool,
+}
+
+/// Read the verified answers for a day. Each part can be written as a string or an integer
+fn read_answers(path: &Path, input: &Path) -> Option<Answers> {
+    let contents = fs::read_to_string(path).ok()?;
+    let table = match contents.parse::<toml::Table>() {
+        Ok(table) => table,
+        Err(e) => {
+            println!(
+                "cargo:warning=Failed to parse answers file \"{}\": {}",
+                path.display(),
+                e
+            );
+            return None;
         }
     };
+    let part = |key| match table.get(key) {
+        Some(toml::Value::String(s)) => Some(s.clone()),
+        Some(toml::Value::Integer(i)) => Some(i.to_string()),
+        Some(v) => {
+            println!(
+                "cargo:warning=Invalid answer for {} in \"{}\": {}",
+                key,
+                path.display(),
+                v
+            );
+            None
+        }
+        None => None,
+    };
+    Some(Answers {
+        part1: part("part1"),
+        part2: part("part2"),
+        has_input: input.exists(),
+    })
 }
 
-const HEADER: &str = r"// ============================================================================
-// DO NOT MODIFY THIS FILE!
-// IT WILL BE OVERWRITTEN EVERY TIME THE SOURCE IS BUILT!
-// ============================================================================
-";
+/// The names of the features declared in `Cargo.toml`
+fn read_features() -> HashSet<String> {
+    let manifest = match fs::read_to_string("Cargo.toml").map(|s| s.parse::<toml::Table>()) {
+        Ok(Ok(manifest)) => manifest,
+        Ok(Err(e)) => {
+            println!("cargo:warning=Failed to parse Cargo.toml: {}", e);
+            return HashSet::new();
+        }
+        Err(e) => {
+            println!("cargo:warning=Failed to read Cargo.toml: {}", e);
+            return HashSet::new();
+        }
+    };
+    manifest
+        .get("features")
+        .and_then(|f| f.as_table())
+        .map(|f| f.keys().cloned().collect())
+        .unwrap_or_default()
+}
 
 fn main() {
     // Tell Cargo that if the given file changes, to rerun this build script.
     println!("cargo:rerun-if-changed=src");
-    let mut prev_year = OsString::new();
-    let mut file: Option<File> = None;
-    let mut years: Vec<String> = Vec::new();
-    let mut days: Vec<String> = Vec::new();
+    if Path::new("answers").exists() {
+        println!("cargo:rerun-if-changed=answers");
+    }
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    // The same as `InputSource::default_dir`, so that answer tests are only run if there is an
+    // input to check them against
+    println!("cargo:rerun-if-env-changed=AOC_INPUT_DIR");
+    let input_dir = env::var_os("AOC_INPUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| manifest_dir.join("../input"));
+    if input_dir.exists() {
+        println!("cargo:rerun-if-changed={}", input_dir.display());
+    }
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    let mut years: BTreeMap<String, Vec<Day>> = BTreeMap::new();
     for path in glob::glob("./src/y*/d*")
         .expect("Failed to read glob pattern")
-        .filter_map(|p| p.map(|p| p.with_extension("")).ok())
+        .filter_map(|p| p.ok())
     {
+        let file = manifest_dir.join(&path);
+        let path = path.with_extension("");
         let mut comp = path.components();
-        let y_name = comp.nth(1).unwrap().as_os_str();
-        if prev_year != y_name {
-            write_run_day(&file, &mut days);
-            file = fs::File::create(path.parent().unwrap().join("mod.rs")).ok();
-            prev_year = y_name.to_owned();
-            years.push(y_name.to_str().unwrap().to_string());
-            write_to_file!(file, "{}", HEADER);
-        }
+        let y_name = comp.nth(1).unwrap().as_os_str().to_str().unwrap();
         let d_name = comp.next().unwrap().as_os_str().to_str().unwrap();
-        days.push(d_name.to_string());
-        write_to_file!(file, "pub mod {};", d_name);
+        years.entry(y_name.to_string()).or_default().push(Day {
+            name: d_name.to_string(),
+            file,
+            answers: read_answers(
+                &Path::new("./answers")
+                    .join(&y_name[1..])
+                    .join(format!("{}.toml", &d_name[1..])),
+                &input_dir
+                    .join(&y_name[1..])
+                    .join(format!("{}.txt", &d_name[1..])),
+            ),
+        });
+    }
+    let features = read_features();
+    let mut enabled = Vec::new();
+    for (year, days) in years.iter() {
+        let module = manifest_dir.join("src").join(year).join("mod.rs");
+        if !module.exists() {
+            println!(
+                "cargo:warning=src/{0}/mod.rs is missing, so the solutions in src/{0} will not be compiled. It should contain `include!(concat!(env!(\"OUT_DIR\"), \"/{0}.rs\"));`",
+                year
+            );
+        } else if !features.contains(year) {
+            println!(
+                "cargo:warning=There is no `{0}` feature in Cargo.toml, so the solutions in src/{0} will not be compiled. Add `{0} = []` to the [features] table",
+                year
+            );
+        } else {
+            enabled.push((year.as_str(), module, days.as_slice()));
+        }
+        let path = out_dir.join(format!("{}.rs", year));
+        if let Err(e) = write_year(&path, year, days) {
+            println!(
+                "cargo:warning=Failed writing to \"{}\": {:?}",
+                path.display(),
+                e
+            );
+        }
     }
-    write_run_day(&file, &mut days);
-    if let Err(e) = write_main(years) {
-        println!("cargo:warning=Failed writing to \"main.rs\": {:?}", e);
+    let path = out_dir.join("years.rs");
+    if let Err(e) = write_years(&path, &enabled, years.keys()) {
+        println!(
+            "cargo:warning=Failed writing to \"{}\": {:?}",
+            path.display(),
+            e
+        );
     }
 }
 
-fn write_run_day(file: &Option<File>, days: &mut Vec<String>) {
-    if days.len() > 0 {
-        if file.is_some() {
-            write_to_file!(
-                file,
-                "\npub fn run_day(day: u8, part: crate::RunPart) {{\n    match day {{"
-            );
-            for day in days.iter() {
-                write_to_file!(
-                    file,
-                    "        {} => {}::main(part),",
-                    (&day[1..]).parse::<u8>().unwrap(),
-                    day
-                );
-            }
-            write_to_file!(file, "        _ => panic!(\"Invalid day: {{}} does not have a solution\", day),\n    }}\n}}");
+/// Write the day modules and the answer tests for a year. Included by `src/yXXXX/mod.rs`
+fn write_year(path: &Path, year: &str, days: &[Day]) -> Result<(), io::Error> {
+    let mut f = File::create(path)?;
+    write!(f, "{}", HEADER)?;
+    for day in days.iter() {
+        writeln!(
+            f,
+            "#[path = {:?}]\npub mod {};",
+            day.file.display().to_string(),
+            day.name
+        )?;
+    }
+    write_answer_tests(&mut f, &year[1..], days)
+}
+
+fn write_answer_tests(f: &mut File, year: &str, days: &[Day]) -> Result<(), io::Error> {
+    fn quote(value: &Option<String>) -> String {
+        value
+            .as_ref()
+            .map_or("None".to_string(), |a| format!("Some({:?})", a))
+    }
+    let days = days
+        .iter()
+        .filter_map(|day| day.answers.as_ref().map(|answers| (&day.name, answers)))
+        .collect::<Vec<_>>();
+    if !days.is_empty() {
+        writeln!(f, "\n#[cfg(test)]\nmod answers {{")?;
+        for (day, answers) in days {
+            writeln!(
+                f,
+                "    #[test]{5}\n    fn {1}() {{\n        crate::answers::check({0}, {2}, super::{1}::main, {3}, {4});\n    }}",
+                year,
+                day,
+                day[1..].parse::<u8>().unwrap(),
+                quote(&answers.part1),
+                quote(&answers.part2),
+                if answers.has_input {
+                    ""
+                } else {
+                    "\n    #[ignore = \"input missing\"]"
+                }
+            )?;
         }
-        *days = Vec::new();
+        writeln!(f, "}}")?;
     }
+    Ok(())
 }
 
-fn write_main(years: Vec<String>) -> Result<(), io::Error> {
-    if years.len() > 0 {
-        let mut f = fs::File::create("./src/main.rs").unwrap();
-        write!(
+/// Write the year modules, `YEARS`, `ALL_YEARS` and `SOLUTIONS`. Included by `src/lib.rs`.
+/// `years` are those which have a feature declared (along with their `mod.rs`),
+/// `all_years` includes those which do not
+fn write_years<'a>(
+    path: &Path,
+    years: &[(&str, PathBuf, &[Day])],
+    all_years: impl Iterator<Item = &'a String>,
+) -> Result<(), io::Error> {
+    let mut f = File::create(path)?;
+    writeln!(
+        f,
+        "{}\n/// The years which have been compiled in, in order\npub const YEARS: &[u16] = &[",
+        HEADER
+    )?;
+    for (year, _, _) in years.iter() {
+        writeln!(
             f,
-            r"{}
-mod cli;
-pub use cli::RunPart;
-mod dispatch;
-#[allow(unused_imports)]
-use dispatch::*;
-mod dispatcher;
-#[allow(unused_imports)]
-use dispatcher::*;
+            "    #[cfg(feature = \"{1}\")]\n    {},",
+            year[1..].parse::<u16>().unwrap(),
+            year
+        )?;
+    }
+    writeln!(
+        f,
+        r"];
 
-fn main() {{
-    cli::main()
-}}
+/// Every year which has solutions, including those which have not been compiled in
+pub const ALL_YEARS: &[u16] = &[{}];
 
-pub fn run(year: u16, day: u8, part: crate::RunPart) {{
-    match year {{
-",
-            HEADER
-        )?;
-        for year in years.iter() {
+/// Every solution which has been compiled in, ordered by year and day
+pub static SOLUTIONS: &[SolutionInfo] = &[",
+        all_years
+            .map(|year| year[1..].to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )?;
+    for (year, _, days) in years.iter() {
+        for day in days.iter() {
+            let module = format!("{}::{}", year, day.name);
             writeln!(
                 f,
-                "        #[cfg(feature = \"{1}\")]\n        {} => {}::run_day(day, part),",
-                (&year[1..]).parse::<u16>().unwrap(),
-                year
+                r#"    #[cfg(feature = "{year}")]
+    SolutionInfo {{
+        year: {},
+        day: {},
+        title: {module}::TITLE,
+        parts_implemented: {module}::PARTS,
+        runner: {module}::main,
+        examples: {module}::EXAMPLES,
+        params: {module}::PARAMS,
+    }},"#,
+                year[1..].parse::<u16>().unwrap(),
+                day.name[1..].parse::<u8>().unwrap(),
             )?;
         }
+    }
+    writeln!(f, "];")?;
+    for (year, module, _) in years {
         writeln!(
             f,
-            r#"        _ => panic!("Invalid year: no solutions have been written for {{}}", year),
-    }}
-}}"#
+            "#[cfg(feature = \"{0}\")]\n#[path = {1:?}]\npub mod {0};",
+            year,
+            module.display().to_string()
         )?;
-        for year in years {
-            writeln!(f, "#[cfg(feature = \"{0}\")]\npub mod {0};", year)?;
-        }
     }
     Ok(())
 }
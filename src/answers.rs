@@ -0,0 +1,45 @@
+use crate::{InputSource, Params, RunPart, RunReport};
+
+/// Run `main` against the real input for the day (with the default parameters) and compare the answers with the verified ones
+/// recorded in `answers/<yyyy>/<dd>.toml`. Called by the tests generated by the build script.
+/// The build script marks the test as ignored if the input file is not present
+#[allow(dead_code)] // Unused if no answers have been recorded
+pub fn check(
+    year: u16,
+    day: u8,
+    main: fn(&str, RunPart, &Params) -> RunReport,
+    part1: Option<&str>,
+    part2: Option<&str>,
+) {
+    let source = InputSource::default();
+    let input = source
+        .load(year, day)
+        .unwrap_or_else(|e| panic!("{} day {}: {}", year, day, e));
+    let parts = match (part1, part2) {
+        (Some(_), None) => RunPart::Part1,
+        (None, Some(_)) => RunPart::Part2,
+        _ => RunPart::All,
+    };
+    let report = main(&input, parts, &Params::new());
+    if let Some(failure) = &report.failure {
+        panic!("{} day {} failed. {}", year, day, failure);
+    }
+    if let Some(expected) = part1 {
+        assert_eq!(
+            report.answer(1).map(str::trim),
+            Some(expected),
+            "{} day {} part 1",
+            year,
+            day
+        );
+    }
+    if let Some(expected) = part2 {
+        assert_eq!(
+            report.answer(2).map(str::trim),
+            Some(expected),
+            "{} day {} part 2",
+            year,
+            day
+        );
+    }
+}
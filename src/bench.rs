@@ -0,0 +1,181 @@
+use std::time::{Duration, Instant};
+
+use crate::{report::print_table, RunPart, RunReport};
+
+/// How many times to run a solution when benchmarking
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Untimed runs before the measured ones
+    pub warmup: usize,
+    /// Stop after this many measured runs
+    pub iterations: Option<usize>,
+    /// Stop once this much time has been spent on measured runs.
+    /// At least one measured run is always made
+    pub budget: Option<Duration>,
+}
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: None,
+            budget: Some(Duration::from_secs(5)),
+        }
+    }
+}
+
+/// Summary statistics for the samples of a single phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+impl Stats {
+    /// Returns `None` if there are no samples
+    pub fn new(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let variance = samples
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Some(Self {
+            min: samples[0],
+            median,
+            mean,
+            p95: samples[((n * 95).div_ceil(100)).max(1) - 1],
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// The results of benchmarking a single day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchReport {
+    pub iterations: usize,
+    /// The wall time taken by the measured runs
+    pub elapsed: Duration,
+    pub setup: Option<Stats>,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+    /// Setup and every part which was run
+    pub total: Stats,
+}
+
+/// Repeatedly run `run` as configured. Each run calls the day's solution from scratch, so the
+/// setup is re-run every iteration (part 2 consumes its result)
+pub fn bench<F>(config: &BenchConfig, run: F) -> BenchReport
+where
+    F: Fn() -> RunReport,
+{
+    for _ in 0..config.warmup {
+        run();
+    }
+    let start = Instant::now();
+    let mut reports = Vec::new();
+    while reports.is_empty()
+        || (config.iterations.is_none_or(|n| reports.len() < n)
+            && config.budget.is_none_or(|b| start.elapsed() < b))
+    {
+        reports.push(run());
+    }
+    let elapsed = start.elapsed();
+    let phase =
+        |f: fn(&RunReport) -> Option<Duration>| Stats::new(reports.iter().filter_map(f).collect());
+    BenchReport {
+        iterations: reports.len(),
+        elapsed,
+        setup: phase(|r| r.setup),
+        part1: phase(|r| r.part1.as_ref().map(|p| p.duration)),
+        part2: phase(|r| r.part2.as_ref().map(|p| p.duration)),
+        total: phase(|r| Some(r.total_duration())).unwrap(),
+    }
+}
+
+pub fn print_report(
+    year: u16,
+    day: u8,
+    parts: RunPart,
+    config: &BenchConfig,
+    report: &BenchReport,
+) {
+    println!(
+        "Benchmarked {} day {} (parts = {:?}): {} iterations in {:?}, after {} warmup runs",
+        year, day, parts, report.iterations, report.elapsed, config.warmup
+    );
+    let rows = [
+        ("Setup", &report.setup),
+        ("Part 1", &report.part1),
+        ("Part 2", &report.part2),
+        ("Total", &Some(report.total)),
+    ]
+    .into_iter()
+    .filter_map(|(name, stats)| {
+        stats.map(|s| {
+            [
+                name.to_string(),
+                format!("{:?}", s.min),
+                format!("{:?}", s.median),
+                format!("{:?}", s.mean),
+                format!("{:?}", s.p95),
+                format!("{:?}", s.stddev),
+            ]
+        })
+    })
+    .collect::<Vec<_>>();
+    print_table(["Phase", "Min", "Median", "Mean", "p95", "Std dev"], &rows);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect()
+    }
+
+    #[test]
+    fn median() {
+        let stats = Stats::new(millis(&[3, 1, 2])).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(2));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        let stats = Stats::new(millis(&[4, 1, 3, 2])).unwrap();
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(Stats::new(Vec::new()), None);
+    }
+
+    #[test]
+    fn p95() {
+        let stats = Stats::new(millis(&[7])).unwrap();
+        assert_eq!(stats.p95, Duration::from_millis(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+        // ceil(20 * 0.95) = 19, so the 19th fastest of 20
+        let stats = Stats::new(millis(&(1..=20).rev().collect::<Vec<_>>())).unwrap();
+        assert_eq!(stats.p95, Duration::from_millis(19));
+    }
+
+    #[test]
+    fn stddev() {
+        // A mean of 5ms, and a (population) variance of 4ms²
+        let stats = Stats::new(millis(&[2, 4, 4, 4, 5, 5, 7, 9])).unwrap();
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        let error = stats.stddev.abs_diff(Duration::from_millis(2));
+        assert!(error < Duration::from_nanos(10), "{:?}", stats.stddev);
+    }
+}
@@ -1,52 +1,101 @@
-use std::{num::ParseIntError, str::FromStr};
+use std::{
+    error::Error,
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
 
-use ::chrono::{Datelike, Utc};
-use clap::{builder::OsStr, command, value_parser, Arg};
+use ::chrono::{DateTime, Datelike, Utc};
+use clap::{builder::OsStr, command, value_parser, Arg, ArgAction, ArgMatches, Command};
 
+use aoc::{
+    bench::{self, BenchConfig},
+    client::{is_downloaded, Client, Verdict},
+    config::Config,
+    history::{AnswerStatus, Entry, History},
+    input::{InputSource, INPUT_DIR_VAR},
+    params::parse_assignment,
+    pool,
+    report::{print_table, JsonReporter, Reporter, TextReporter},
+    scaffold, Params, RunPart, RunReport, SolutionInfo,
+};
+
+use crate::watch::Watch;
+
+/// An inclusive range of days, parsed from `d`, `a..b` or `a..=b`
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum RunPart {
-    All,
-    Part1,
-    Part2,
-}
-impl RunPart {
-    pub fn run_p1(&self) -> bool {
-        if let Self::Part2 = self {
-            false
-        } else {
-            true
+struct DayRange {
+    start: u8,
+    end: u8,
+}
+impl DayRange {
+    fn single(day: u8) -> Self {
+        Self {
+            start: day,
+            end: day,
         }
     }
-    pub fn run_p2(&self) -> bool {
-        if let Self::Part1 = self {
-            false
+    fn contains(&self, day: u8) -> bool {
+        (self.start..=self.end).contains(&day)
+    }
+}
+impl fmt::Display for DayRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "day {}", self.start)
         } else {
-            true
+            write!(f, "days {}..={}", self.start, self.end)
         }
     }
 }
-impl FromStr for RunPart {
+impl FromStr for DayRange {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.chars().next() {
-            Some('1') => Ok(Self::Part1),
-            Some('2') => Ok(Self::Part2),
-            _ => Err(format!("Invalid part number: {}", s)),
+        let parse = |d: &str| {
+            d.parse::<u8>()
+                .map_err(|e| format!("Invalid day \"{}\": {}", d, e))
+        };
+        let empty = || format!("Invalid day range \"{}\": range is empty", s);
+        let (start, end) = if let Some((start, end)) = s.split_once("..=") {
+            (parse(start)?, parse(end)?)
+        } else if let Some((start, end)) = s.split_once("..") {
+            let (start, end) = (parse(start)?, parse(end)?);
+            // Checked before the bounds, as the end of an empty exclusive range may be 1
+            if start >= end {
+                return Err(empty());
+            }
+            (start, end - 1)
+        } else {
+            let day = parse(s)?;
+            (day, day)
+        };
+        if !(1..=25).contains(&start) || !(1..=25).contains(&end) {
+            Err(format!(
+                "Invalid day range \"{}\": days must be from 1 to 25",
+                s
+            ))
+        } else if start > end {
+            Err(empty())
+        } else {
+            Ok(Self { start, end })
         }
     }
 }
 
+#[derive(Debug, Clone, Copy)]
 enum YDArg {
     Y(u16),
-    D(u8),
+    D(DayRange),
 }
 impl FromStr for YDArg {
-    type Err = ParseIntError;
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() == 4 {
-            Ok(Self::Y(s.parse()?))
+        if s.len() == 4 && s.chars().all(|c| c.is_ascii_digit()) {
+            Ok(Self::Y(s.parse().unwrap()))
         } else {
             Ok(Self::D(s.parse()?))
         }
@@ -57,12 +106,18 @@ pub fn main() {
     let today = Utc::now().date_naive();
     let args = command!("aoc")
         .about("Runs the rust solutions to Advent of Code")
+        .after_help(format!("Compiled years: {}", compiled_years()))
         .author("Alpvax <development@alpvax.uk>")
-        .override_usage("aoc [[YYYY] day] [-p 1|2]")
+        .override_usage("aoc [[YYYY] day|--all] [-p 1|2] [--param NAME=VALUE]... [-j N] [-i FILE | --input-dir DIR | -e [NAME]]\n       aoc --all-years [-p 1|2] [-j N] [--input-dir DIR]\n       aoc bench <YYYY> <day> [-p 1|2] [--param NAME=VALUE]... [-n N] [-t SECS] [-w N] [-i FILE | --input-dir DIR]\n       aoc new <YYYY> <day> [--input-dir DIR]\n       aoc fetch <YYYY> <day> [--input-dir DIR]\n       aoc submit <YYYY> <day> <part> [--force] [-i FILE | --input-dir DIR]\n       aoc history <YYYY> <day>\n       aoc watch <YYYY> <day> [-p 1|2] [--param NAME=VALUE]... [--examples] [-i FILE | --input-dir DIR]\n       aoc list [YYYY]")
         .args([
             Arg::new("year")
-                .required(today.month() != 12)
+                .required_unless_present_any(if today.month() != 12 {
+                    &["all-years"][..]
+                } else {
+                    &[]
+                })
                 .default_value(OsStr::from(today.format("%Y").to_string()))
+                .value_parser(|s: &str| s.parse::<YDArg>())
                 .help("Specify the year of the solution to run. Defaults to the current year"),
                 // .value_parser(
                 //     value_parser!(u16).range(
@@ -77,33 +132,849 @@ pub fn main() {
                 // ),
             Arg::new("day")
                 .default_value(OsStr::from(today.format("%d").to_string()))
-                .help("Specify the day of the solution to run. Defaults to today")
-                .value_parser(value_parser!(u8).range(1..=25)),
-            Arg::new("part")
-                .short('p')
-                .long("part")
-                .help("Specify the part of the solution to run. If not specified, both parts are run.\n")
-                .value_parser(["1", "2"]),
+                .help("Specify the day of the solution to run, or a range of days (`1..=10`). Defaults to today")
+                .value_parser(|s: &str| s.parse::<DayRange>()),
+            Arg::new("all")
+                .long("all")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("day")
+                .help("Run every day which has a solution for the year"),
+            Arg::new("all-years")
+                .long("all-years")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["year", "day", "all"])
+                .help("Run every day which has a solution, for every year"),
+            part_arg(),
+            param_arg(),
+        ])
+        .args(input_args())
+        .args([
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .default_value("text")
+                .value_parser(["text", "json"])
+                .help("How to output the results. `json` prints one JSON object per line for each day run"),
+            Arg::new("jobs")
+                .short('j')
+                .long("jobs")
+                .value_name("N")
+                .default_value("1")
+                .value_parser(value_parser!(u64).range(1..))
+                .help("Run up to N days at the same time. The output is still printed in order"),
+            Arg::new("example")
+                .short('e')
+                .long("example")
+                .value_name("NAME")
+                .num_args(0..=1)
+                .default_missing_value("")
+                .conflicts_with_all(["input", "input-dir", "all", "all-years", "format"])
+                .help("Run the example inputs instead of the real input, checking the expected answers. If NAME is specified, only that example is run"),
         ])
+        .subcommand(
+            Command::new("bench")
+                .about("Run a solution repeatedly and report timing statistics for each phase")
+                .args([
+                    Arg::new("year")
+                        .required(true)
+                        .value_parser(value_parser!(u16))
+                        .help("The year of the solution to benchmark"),
+                    Arg::new("day")
+                        .required(true)
+                        .value_parser(value_parser!(u8).range(1..=25))
+                        .help("The day of the solution to benchmark"),
+                    part_arg(),
+                    param_arg(),
+                    Arg::new("iterations")
+                        .short('n')
+                        .long("iterations")
+                        .value_name("N")
+                        .value_parser(value_parser!(usize))
+                        .help("Stop after N measured runs"),
+                    Arg::new("time")
+                        .short('t')
+                        .long("time")
+                        .value_name("SECS")
+                        .value_parser(value_parser!(f64))
+                        .help("Stop after SECS seconds of measured runs. Defaults to 5 unless --iterations is specified"),
+                    Arg::new("warmup")
+                        .short('w')
+                        .long("warmup")
+                        .value_name("N")
+                        .default_value("3")
+                        .value_parser(value_parser!(usize))
+                        .help("The number of untimed runs before measuring"),
+                ])
+                .args(input_args()),
+        )
+        .subcommand(
+            Command::new("new")
+                .about("Create a new day module from a template, along with an empty input file")
+                .args([
+                    Arg::new("year")
+                        .required(true)
+                        .value_parser(value_parser!(u16))
+                        .help("The year of the day to create"),
+                    Arg::new("day")
+                        .required(true)
+                        .value_parser(value_parser!(u8).range(1..=25))
+                        .help("The day to create"),
+                    input_dir_arg(),
+                ]),
+        )
+        .subcommand(
+            Command::new("fetch")
+                .about("Download the puzzle input for a day, unless it has already been downloaded")
+                .args([
+                    Arg::new("year")
+                        .required(true)
+                        .value_parser(value_parser!(u16))
+                        .help("The year of the input to download"),
+                    Arg::new("day")
+                        .required(true)
+                        .value_parser(value_parser!(u8).range(1..=25))
+                        .help("The day of the input to download"),
+                    input_dir_arg(),
+                ]),
+        )
+        .subcommand(
+            Command::new("submit")
+                .about("Run a solution and submit the answer for one part, recording the response in the answer history")
+                .args([
+                    Arg::new("year")
+                        .required(true)
+                        .value_parser(value_parser!(u16))
+                        .help("The year of the solution to submit"),
+                    Arg::new("day")
+                        .required(true)
+                        .value_parser(value_parser!(u8).range(1..=25))
+                        .help("The day of the solution to submit"),
+                    Arg::new("part")
+                        .required(true)
+                        .value_parser(value_parser!(u8).range(1..=2))
+                        .help("The part to submit"),
+                    Arg::new("force")
+                        .long("force")
+                        .action(ArgAction::SetTrue)
+                        .help("Submit the answer even if the history says it is wrong"),
+                ])
+                .args(input_args()),
+        )
+        .subcommand(
+            Command::new("history")
+                .about("Show the answers recorded for a day, and what is known about the correct ones")
+                .args([
+                    Arg::new("year")
+                        .required(true)
+                        .value_parser(value_parser!(u16))
+                        .help("The year of the day to show"),
+                    Arg::new("day")
+                        .required(true)
+                        .value_parser(value_parser!(u8).range(1..=25))
+                        .help("The day to show"),
+                ]),
+        )
+        .subcommand(
+            Command::new("watch")
+                .about("Rebuild and re-run a solution whenever its source file or input changes, showing how the answers changed")
+                .args([
+                    Arg::new("year")
+                        .required(true)
+                        .value_parser(value_parser!(u16))
+                        .help("The year of the solution to watch"),
+                    Arg::new("day")
+                        .required(true)
+                        .value_parser(value_parser!(u8).range(1..=25))
+                        .help("The day of the solution to watch"),
+                    part_arg(),
+                    param_arg(),
+                    Arg::new("examples")
+                        .long("examples")
+                        .action(ArgAction::SetTrue)
+                        .help("Run the examples first, and only run the real input if they pass"),
+                ])
+                .args(input_args()),
+        )
+        .subcommand(
+            Command::new("list")
+                .about("List the solutions which have been compiled in")
+                .arg(
+                    Arg::new("year")
+                        .value_parser(value_parser!(u16))
+                        .help("Only list the solutions for this year"),
+                ),
+        )
+        .args_conflicts_with_subcommands(true)
+        .subcommand_negates_reqs(true)
         .get_matches();
-    let (year, day) = match args
-        .get_one::<String>("year")
-        .map(|s| s.parse::<YDArg>().unwrap())
-    {
-        Some(YDArg::Y(y)) => (y, *args.get_one::<u8>("day").unwrap()),
-        Some(YDArg::D(d)) => (today.year().try_into().unwrap(), d),
+    match args.subcommand() {
+        Some(("bench", args)) => return run_bench(args),
+        Some(("new", args)) => return new_day(args),
+        Some(("fetch", args)) => return fetch(args),
+        Some(("submit", args)) => return submit(args),
+        Some(("history", args)) => return show_history(args),
+        Some(("list", args)) => return list(args),
+        Some(("watch", args)) => watch(args),
+        _ => (),
+    }
+    let (year, days) = match args.get_one::<YDArg>("year") {
+        Some(YDArg::Y(y)) => (*y, *args.get_one::<DayRange>("day").unwrap()),
+        Some(YDArg::D(d)) => (today.year().try_into().unwrap(), *d),
         None => (
             today.year().try_into().unwrap(),
-            today.day().try_into().unwrap(),
+            DayRange::single(today.day().try_into().unwrap()),
         ),
     };
-    let parts = args
-        .get_one::<String>("part")
+    if !args.get_flag("all-years") {
+        check_year(year);
+    }
+    let selected = if args.get_flag("all-years") {
+        aoc::SOLUTIONS.iter().collect()
+    } else {
+        aoc::solutions(year)
+            .filter(|info| args.get_flag("all") || days.contains(info.day))
+            .collect::<Vec<_>>()
+    };
+    let parts = parts(&args);
+    if let Some(name) = args.get_one::<String>("example") {
+        if days.start != days.end {
+            eprintln!("--example can only be used when running a single day");
+            std::process::exit(1);
+        }
+        run_examples(year, days.start, name, parts, &params(&args, &selected));
+        return;
+    }
+    if selected.is_empty() {
+        if args.get_flag("all-years") {
+            eprintln!("No solutions have been compiled in");
+        } else if args.get_flag("all") {
+            eprintln!("No solutions have been written for {}", year);
+        } else {
+            eprintln!("No solutions have been written for {} {}", year, days);
+        }
+        std::process::exit(1);
+    }
+    let source = input_source(&args);
+    if selected.len() > 1 && !matches!(source, InputSource::Dir(_)) {
+        eprintln!("--input can only be used when running a single day");
+        std::process::exit(1);
+    }
+    let params = params(&args, &selected);
+    let mut reporter: Box<dyn Reporter> = match args.get_one::<String>("format").unwrap().as_str() {
+        "json" => Box::new(JsonReporter),
+        _ => Box::new(TextReporter::default()),
+    };
+    // Answers for other inputs (or with different parameters) are not recorded in the history
+    let history_dir = match (&source, Config::load()) {
+        (InputSource::Dir(_), Ok(config)) if params.is_empty() => Some(config.history_dir),
+        (InputSource::Dir(_), Err(e)) if params.is_empty() => {
+            eprintln!("Not checking the answer history. {}", e);
+            None
+        }
+        _ => None,
+    };
+    let jobs = *args.get_one::<u64>("jobs").unwrap() as usize;
+    let start = Instant::now();
+    let mut failed = false;
+    let mut ran = 0;
+    // Each day is loaded and run on a worker thread, then reported here in order
+    pool::run_ordered(
+        &selected,
+        jobs,
+        |info| {
+            load_input(&source, info.year, info.day)
+                .map(|input| info.run_with(&input, parts, &params))
+                .map_err(|e| e.to_string())
+        },
+        |info, result| {
+            let (year, day) = (info.year, info.day);
+            let report = match result {
+                Ok(report) => report,
+                Err(e) if selected.len() > 1 => {
+                    eprintln!("Skipping {} day {}: {}", year, day, e);
+                    failed = true;
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            };
+            if parts == RunPart::Part2 && info.parts_implemented < 2 {
+                eprintln!("Part 2 of {} day {} has not been written yet", year, day);
+            }
+            reporter.start(year, day, parts);
+            ran += 1;
+            failed |= report.failure.is_some();
+            reporter.report(year, day, &report);
+            if let Some(dir) = &history_dir {
+                check_history(dir, year, day, &report);
+            }
+        },
+    );
+    reporter.finish();
+    if jobs > 1 && selected.len() > 1 && args.get_one::<String>("format").unwrap() == "text" {
+        println!(
+            "Wall clock time on {} threads: {:?}",
+            jobs.min(selected.len()),
+            start.elapsed()
+        );
+    }
+    if ran == 0 {
+        eprintln!("No days were run, as none of their inputs could be loaded");
+    }
+    // Also exits with an error if any input could not be loaded
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// Record the answers in the history, marking those which match the accepted answer and warning
+/// about those which are known to be wrong
+fn check_history(dir: &Path, year: u16, day: u8, report: &RunReport) {
+    let mut history = match History::load(dir, year, day) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+    for part in [1, 2] {
+        let Some(answer) = report.answer(part).map(str::trim) else {
+            continue;
+        };
+        let status = history.status(part, answer);
+        if status == AnswerStatus::Accepted {
+            eprintln!("{} day {} part {}: {} {}", year, day, part, answer, status);
+        } else if status.is_wrong() {
+            eprintln!(
+                "Warning: {} day {} part {}: {} {}",
+                year, day, part, answer, status
+            );
+        }
+        if let Err(e) = history.record_answer(part, answer) {
+            eprintln!("{}", e);
+        }
+    }
+}
+
+fn compiled_years() -> String {
+    if aoc::YEARS.is_empty() {
+        "none".to_string()
+    } else {
+        aoc::YEARS
+            .iter()
+            .map(|y| y.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Exit with an error if the solutions for `year` have not been compiled in
+fn check_year(year: u16) {
+    if !aoc::YEARS.contains(&year) {
+        if aoc::ALL_YEARS.contains(&year) {
+            eprintln!(
+                "The solutions for {0} have not been compiled in. Rebuild with `--features y{0}`",
+                year
+            );
+        } else {
+            eprintln!("No solutions have been written for {}", year);
+        }
+        eprintln!("Compiled years: {}", compiled_years());
+        std::process::exit(1);
+    }
+}
+
+fn part_arg() -> Arg {
+    Arg::new("part")
+        .short('p')
+        .long("part")
+        .help("Specify the part of the solution to run. If not specified, both parts are run.\n")
+        .value_parser(["1", "2"])
+}
+
+fn param_arg() -> Arg {
+    Arg::new("param")
+        .long("param")
+        .value_name("NAME=VALUE")
+        .action(ArgAction::Append)
+        .value_parser(parse_assignment)
+        .help("Override a parameter of the solution, such as a size which is different for the examples. Can be repeated. `aoc list` shows the parameters of each day")
+}
+
+fn input_args() -> [Arg; 2] {
+    [
+        Arg::new("input")
+            .short('i')
+            .long("input")
+            .value_name("FILE")
+            .help("Read the puzzle input from FILE instead of the input directory. Use `-` to read from stdin"),
+        input_dir_arg().conflicts_with("input"),
+    ]
+}
+
+fn input_dir_arg() -> Arg {
+    Arg::new("input-dir")
+        .long("input-dir")
+        .value_name("DIR")
+        .value_parser(value_parser!(PathBuf))
+        .help(format!(
+            "The directory containing the puzzle inputs, laid out as `<DIR>/<yyyy>/<dd>.txt`. Defaults to ${} or `../input`",
+            INPUT_DIR_VAR
+        ))
+}
+
+fn parts(args: &ArgMatches) -> RunPart {
+    args.get_one::<String>("part")
         .map(|s| s.parse::<RunPart>().unwrap())
-        .unwrap_or(RunPart::All);
+        .unwrap_or(RunPart::All)
+}
+
+/// The `--param` overrides. Exits if any of them are not declared by one of the selected days
+fn params(args: &ArgMatches, selected: &[&SolutionInfo]) -> Params {
+    let params = args
+        .get_many::<(String, String)>("param")
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect::<Params>();
+    for name in params.names() {
+        if !selected.iter().any(|info| info.param(name).is_some()) {
+            let mut available = selected
+                .iter()
+                .flat_map(|info| info.params)
+                .map(|p| format!("\"{}\"", p.name))
+                .collect::<Vec<_>>();
+            available.sort_unstable();
+            available.dedup();
+            if available.is_empty() {
+                eprintln!(
+                    "Unknown parameter \"{}\": the solution has no parameters",
+                    name
+                );
+            } else {
+                eprintln!(
+                    "Unknown parameter \"{}\". Available parameters: {}",
+                    name,
+                    available.join(", ")
+                );
+            }
+            std::process::exit(1);
+        }
+    }
+    params
+}
+
+fn input_source(args: &ArgMatches) -> InputSource {
+    match (
+        args.get_one::<String>("input"),
+        args.get_one::<PathBuf>("input-dir"),
+    ) {
+        (Some(file), _) => InputSource::from_arg(file),
+        (None, Some(dir)) => InputSource::Dir(dir.clone()),
+        (None, None) => InputSource::default(),
+    }
+}
+
+/// Load the input for a day. If it is missing from the input directory it is downloaded first,
+/// unless `auto_fetch` is disabled or no session has been configured
+fn load_input(source: &InputSource, year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+    // Shared, so that requests are throttled across days
+    static CLIENT: OnceLock<Option<Client>> = OnceLock::new();
+    if let (InputSource::Dir(_), Some(path)) = (source, source.path(year, day)) {
+        if !is_downloaded(&path) {
+            let client = match CLIENT.get() {
+                Some(client) => client,
+                None => {
+                    let config = Config::load()?;
+                    CLIENT.get_or_init(|| {
+                        (config.auto_fetch && config.session.is_some())
+                            .then(|| Client::new(&config))
+                    })
+                }
+            };
+            if let Some(client) = client {
+                eprintln!(
+                    "Downloading the input for {} day {} to {}",
+                    year,
+                    day,
+                    path.display()
+                );
+                client.download_input(year, day, &path)?;
+            }
+        }
+    }
+    Ok(source.load(year, day)?)
+}
+
+fn fetch(args: &ArgMatches) {
+    let year = *args.get_one::<u16>("year").unwrap();
+    let day = *args.get_one::<u8>("day").unwrap();
+    let input = args
+        .get_one::<PathBuf>("input-dir")
+        .map_or_else(InputSource::default, |dir| InputSource::Dir(dir.clone()));
+    let path = input.path(year, day).unwrap();
+    let result = Config::load()
+        .map_err(Box::<dyn Error>::from)
+        .and_then(|config| Ok(Client::new(&config).download_input(year, day, &path)?));
+    match result {
+        Ok(true) => println!(
+            "Downloaded the input for {} day {} to {}",
+            year,
+            day,
+            path.display()
+        ),
+        Ok(false) => println!("{} already exists", path.display()),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_bench(args: &ArgMatches) {
+    let year = *args.get_one::<u16>("year").unwrap();
+    let day = *args.get_one::<u8>("day").unwrap();
+    let parts = parts(args);
+    check_year(year);
+    let Some(info) = aoc::solution(year, day) else {
+        eprintln!("No solution has been written for {} day {}", year, day);
+        std::process::exit(1);
+    };
+    let input = match load_input(&input_source(args), year, day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let params = params(args, &[info]);
+    let iterations = args.get_one::<usize>("iterations").copied();
+    let config = BenchConfig {
+        warmup: *args.get_one::<usize>("warmup").unwrap(),
+        iterations,
+        budget: match args.get_one::<f64>("time") {
+            Some(secs) => Some(Duration::from_secs_f64(*secs)),
+            None if iterations.is_some() => None,
+            None => BenchConfig::default().budget,
+        },
+    };
     println!(
-        "Running: year = {:?}, day = {:?}, parts = {:?}",
+        "Benchmarking: year = {:?}, day = {:?}, parts = {:?}",
         year, day, parts
     );
-    crate::run(year, day, parts);
+    let report = bench::bench(&config, || {
+        let report = info.run_with(&input, parts, &params);
+        if let Some(failure) = &report.failure {
+            eprintln!("{} day {} failed. {}", year, day, failure);
+            std::process::exit(1);
+        }
+        report
+    });
+    bench::print_report(year, day, parts, &config, &report);
+}
+
+fn new_day(args: &ArgMatches) {
+    let year = *args.get_one::<u16>("year").unwrap();
+    let day = *args.get_one::<u8>("day").unwrap();
+    let input = args
+        .get_one::<PathBuf>("input-dir")
+        .map_or_else(InputSource::default, |dir| InputSource::Dir(dir.clone()));
+    match scaffold::new_day(year, day, &input) {
+        Ok(created) => {
+            if let Some(dir) = created.year_dir {
+                println!("Created {}", dir.display());
+                println!(
+                    "Add `y{0} = []` to the [features] table in Cargo.toml to compile the solutions for {0}",
+                    year
+                );
+            }
+            println!("Created {}", created.day_file.display());
+            if let Some(input) = created.input_file {
+                println!("Created empty input file {}", input.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn submit(args: &ArgMatches) {
+    let year = *args.get_one::<u16>("year").unwrap();
+    let day = *args.get_one::<u8>("day").unwrap();
+    let part = *args.get_one::<u8>("part").unwrap();
+    check_year(year);
+    let Some(info) = aoc::solution(year, day) else {
+        eprintln!("No solution has been written for {} day {}", year, day);
+        std::process::exit(1);
+    };
+    let exit = |e: &dyn std::fmt::Display| -> ! {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    };
+    let config = Config::load().unwrap_or_else(|e| exit(&e));
+    let input = load_input(&input_source(args), year, day).unwrap_or_else(|e| exit(&*e));
+    let report = info.run(
+        &input,
+        if part == 1 {
+            RunPart::Part1
+        } else {
+            RunPart::Part2
+        },
+    );
+    if let Some(failure) = &report.failure {
+        exit(&format!("{} day {} failed. {}", year, day, failure));
+    }
+    let Some(answer) = report.answer(part).map(str::trim) else {
+        exit(&format!(
+            "Part {} of {} day {} has not been written yet",
+            part, year, day
+        ));
+    };
+    println!("Part {}: {}", part, answer);
+    let mut history = History::load(&config.history_dir, year, day).unwrap_or_else(|e| exit(&e));
+    let status = history.status(part, answer);
+    if status == AnswerStatus::Accepted {
+        println!("This answer has already been accepted");
+        return;
+    }
+    if !args.get_flag("force") {
+        if status.is_wrong() {
+            exit(&format!(
+                "Not submitting: {} {}. Use --force to submit it anyway",
+                answer, status
+            ));
+        }
+        if let Some(verdict) = history.verdict(part, answer) {
+            exit(&format!(
+                "This answer has already been submitted. {}",
+                verdict
+            ));
+        }
+    }
+    let verdict = Client::new(&config)
+        .submit(year, day, part, answer)
+        .unwrap_or_else(|e| exit(&e));
+    println!("{}", verdict);
+    if let Err(e) = history.record(Entry::new(part, answer, Some(verdict.clone()))) {
+        eprintln!("{}", e);
+    }
+    if verdict != Verdict::Correct {
+        std::process::exit(1);
+    }
+}
+
+fn show_history(args: &ArgMatches) {
+    let year = *args.get_one::<u16>("year").unwrap();
+    let day = *args.get_one::<u8>("day").unwrap();
+    let history = match Config::load()
+        .map_err(Box::<dyn Error>::from)
+        .and_then(|config| Ok(History::load(&config.history_dir, year, day)?))
+    {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    if history.entries.is_empty() {
+        println!("No answers have been recorded for {} day {}", year, day);
+        return;
+    }
+    let rows = history
+        .entries
+        .iter()
+        .map(|entry| {
+            [
+                DateTime::parse_from_rfc3339(&entry.timestamp).map_or_else(
+                    |_| entry.timestamp.clone(),
+                    |t| t.format("%Y-%m-%d %H:%M:%S").to_string(),
+                ),
+                entry.part.to_string(),
+                if entry.answer.contains('\n') {
+                    format!("({} lines)", entry.answer.lines().count())
+                } else {
+                    entry.answer.clone()
+                },
+                match &entry.verdict {
+                    None => "not submitted",
+                    Some(Verdict::Correct) => "correct",
+                    Some(Verdict::Incorrect) => "wrong",
+                    Some(Verdict::TooHigh) => "too high",
+                    Some(Verdict::TooLow) => "too low",
+                    Some(Verdict::RateLimited { .. }) => "rate limited",
+                    Some(Verdict::WrongLevel) => "wrong level",
+                }
+                .to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    print_table(["Time", "Part", "Answer", "Verdict"], &rows);
+    for part in [1, 2] {
+        match (history.correct(part), history.bounds(part)) {
+            (Some(correct), _) => println!("Part {}: {} was accepted", part, correct),
+            (None, bounds) if !bounds.is_unbounded() => {
+                println!("Part {}: the answer is {}", part, bounds)
+            }
+            _ => (),
+        }
+    }
+}
+
+fn list(args: &ArgMatches) {
+    let year = args.get_one::<u16>("year").copied();
+    if let Some(year) = year {
+        check_year(year);
+    }
+    let rows = aoc::SOLUTIONS
+        .iter()
+        .filter(|info| year.is_none_or(|y| info.year == y))
+        .map(|info| {
+            [
+                info.year.to_string(),
+                info.day.to_string(),
+                info.title.to_string(),
+                (1..=info.parts_implemented)
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                info.examples.len().to_string(),
+                info.params
+                    .iter()
+                    .map(|p| format!("{}={}", p.name, p.default))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ]
+        })
+        .collect::<Vec<_>>();
+    if rows.is_empty() {
+        println!("No solutions have been compiled in");
+    } else {
+        print_table(
+            ["Year", "Day", "Title", "Parts", "Examples", "Params"],
+            &rows,
+        );
+    }
+    if year.is_none() {
+        for year in aoc::ALL_YEARS.iter().filter(|y| !aoc::YEARS.contains(y)) {
+            println!(
+                "The solutions for {0} have not been compiled in. Rebuild with `--features y{0}`",
+                year
+            );
+        }
+    }
+}
+
+fn watch(args: &ArgMatches) -> ! {
+    let input = input_source(args);
+    if input == InputSource::Stdin {
+        eprintln!("The input cannot be read from stdin when watching");
+        std::process::exit(1);
+    }
+    Watch {
+        year: *args.get_one::<u16>("year").unwrap(),
+        day: *args.get_one::<u8>("day").unwrap(),
+        parts: parts(args),
+        params: args
+            .get_many::<(String, String)>("param")
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect(),
+        input,
+        examples: args.get_flag("examples"),
+    }
+    .run()
+}
+
+/// Run the examples for a day. `params` override the values set by each example
+fn run_examples(year: u16, day: u8, name: &str, parts: RunPart, params: &Params) {
+    let Some(info) = aoc::solution(year, day) else {
+        eprintln!("No solution has been written for {} day {}", year, day);
+        std::process::exit(1);
+    };
+    let examples = info.examples;
+    let selected = examples
+        .iter()
+        .filter(|e| name.is_empty() || e.name == name)
+        .collect::<Vec<_>>();
+    if selected.is_empty() {
+        if examples.is_empty() {
+            eprintln!("No examples have been added for year {}, day {}", year, day);
+        } else {
+            eprintln!(
+                "No example named \"{}\". Available examples: {}",
+                name,
+                examples
+                    .iter()
+                    .map(|e| format!("\"{}\"", e.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        std::process::exit(1);
+    }
+    let mut failed = false;
+    for example in selected {
+        let Some(parts) = example.parts_for(parts) else {
+            println!(
+                "Skipping example \"{}\": no expected answer for {:?}",
+                example.name, parts
+            );
+            continue;
+        };
+        println!(
+            "Running example \"{}\": year = {:?}, day = {:?}, parts = {:?}",
+            example.name, year, day, parts
+        );
+        let mut example_params = example.params();
+        example_params.extend(params.names().map(|name| (name, params.get(name).unwrap())));
+        let report = info.run_with(example.input, parts, &example_params);
+        TextReporter::default().report(year, day, &report);
+        for (part, expected, actual) in example.check(&report) {
+            failed = true;
+            println!(
+                "Part {} is incorrect! Expected {}, got {}",
+                part,
+                expected,
+                actual.as_deref().unwrap_or("nothing")
+            );
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_day_range() {
+        let range = |start, end| Ok(DayRange { start, end });
+        assert_eq!("5".parse(), range(5, 5));
+        assert_eq!("1..=3".parse(), range(1, 3));
+        assert_eq!("1..4".parse(), range(1, 3));
+        assert_eq!(
+            "5..5".parse::<DayRange>(),
+            Err("Invalid day range \"5..5\": range is empty".to_string())
+        );
+        assert_eq!(
+            "1..1".parse::<DayRange>(),
+            Err("Invalid day range \"1..1\": range is empty".to_string())
+        );
+        assert_eq!(
+            "0..=3".parse::<DayRange>(),
+            Err("Invalid day range \"0..=3\": days must be from 1 to 25".to_string())
+        );
+        assert_eq!(
+            "24..=26".parse::<DayRange>(),
+            Err("Invalid day range \"24..=26\": days must be from 1 to 25".to_string())
+        );
+        assert!("x..=3".parse::<DayRange>().is_err());
+    }
 }
@@ -0,0 +1,416 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+
+/// The default `User-Agent`, as asked for by the Advent of Code automation guidelines
+pub const USER_AGENT: &str = concat!(
+    "aoc-rs/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/Alpvax/AoC-rs by development@alpvax.uk)"
+);
+
+/// The server's response to a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    /// Wrong, without a hint as to which direction
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, so this one was not checked
+    RateLimited {
+        wait_secs: Option<u64>,
+    },
+    /// The part has already been solved (or part 1 has not been solved yet)
+    WrongLevel,
+}
+impl Verdict {
+    /// Whether the server said the answer was wrong
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Self::Incorrect | Self::TooHigh | Self::TooLow)
+    }
+}
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "That's the right answer!"),
+            Self::Incorrect => write!(f, "That's not the right answer"),
+            Self::TooHigh => write!(f, "That's not the right answer: it is too high"),
+            Self::TooLow => write!(f, "That's not the right answer: it is too low"),
+            Self::RateLimited { wait_secs: Some(s) } => {
+                write!(f, "An answer was submitted too recently. Wait {}s", s)
+            }
+            Self::RateLimited { wait_secs: None } => {
+                write!(f, "An answer was submitted too recently")
+            }
+            Self::WrongLevel => write!(
+                f,
+                "Not solving the right level. Has the part already been completed?"
+            ),
+        }
+    }
+}
+
+/// Work out the verdict from the body of the response to a submission
+pub fn parse_verdict(body: &str) -> Option<Verdict> {
+    lazy_static! {
+        static ref WAIT: Regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    }
+    if body.contains("That's the right answer") {
+        Some(Verdict::Correct)
+    } else if body.contains("That's not the right answer") {
+        Some(if body.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if body.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        })
+    } else if body.contains("You gave an answer too recently") {
+        let wait_secs = WAIT.captures(body).map(|c| {
+            c.get(1).map_or(0, |m| m.as_str().parse::<u64>().unwrap()) * 60
+                + c[2].parse::<u64>().unwrap()
+        });
+        Some(Verdict::RateLimited { wait_secs })
+    } else if body.contains("You don't seem to be solving the right level") {
+        Some(Verdict::WrongLevel)
+    } else {
+        None
+    }
+}
+
+/// When the puzzle for the given day is released: midnight US Eastern time (UTC-5)
+pub fn unlock_time(year: u16, day: u8) -> DateTime<Utc> {
+    NaiveDate::from_ymd_opt(year.into(), 12, day.into())
+        .and_then(|date| date.and_hms_opt(5, 0, 0))
+        .expect("invalid puzzle date")
+        .and_utc()
+}
+
+/// Whether an input file exists. Empty files (as created by `aoc new`) are treated as missing
+pub fn is_downloaded(path: &Path) -> bool {
+    path.metadata().is_ok_and(|m| m.len() > 0)
+}
+
+/// Talks to an Advent of Code compatible server.
+/// Requests are spaced out by at least the configured interval
+#[derive(Debug)]
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: Option<String>,
+    interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+impl Client {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(&config.user_agent)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: config.base_url.clone(),
+            session: config.session.clone(),
+            interval: Duration::from_millis(config.request_interval_ms),
+            last_request: Mutex::new(None),
+        }
+    }
+    pub fn has_session(&self) -> bool {
+        self.session.is_some()
+    }
+    fn cookie(&self) -> Result<String, ClientError> {
+        self.session
+            .as_ref()
+            .map(|s| format!("session={}", s))
+            .ok_or(ClientError::MissingSession)
+    }
+    /// Wait until the interval since the previous request has passed
+    fn throttle(&self) {
+        let mut last = self.last_request.lock().unwrap();
+        if let Some(wait) = last.and_then(|t| self.interval.checked_sub(t.elapsed())) {
+            thread::sleep(wait);
+        }
+        *last = Some(Instant::now());
+    }
+    /// Download the puzzle input for a day
+    pub fn fetch_input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let unlock = unlock_time(year, day);
+        if Utc::now() < unlock {
+            return Err(ClientError::NotReleased(unlock));
+        }
+        let cookie = self.cookie()?;
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        self.throttle();
+        self.agent
+            .get(&url)
+            .set("Cookie", &cookie)
+            .call()
+            .map_err(|e| ClientError::Http(url.clone(), Box::new(e)))?
+            .into_string()
+            .map_err(|e| ClientError::Body(url, e))
+    }
+    /// Download the puzzle input for a day to `path`, unless it has already been downloaded.
+    /// Returns whether it was downloaded
+    pub fn download_input(&self, year: u16, day: u8, path: &Path) -> Result<bool, ClientError> {
+        if is_downloaded(path) {
+            return Ok(false);
+        }
+        let input = self.fetch_input(year, day)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| ClientError::Io(parent.into(), e))?;
+        }
+        // Written to a temporary file first, so an interrupted write is never mistaken for a
+        // cached input
+        let tmp = path.with_extension("part");
+        fs::write(&tmp, input)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| ClientError::Io(path.into(), e))?;
+        Ok(true)
+    }
+    /// Submit an answer, returning the server's verdict
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, ClientError> {
+        let cookie = self.cookie()?;
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        self.throttle();
+        let body = self
+            .agent
+            .post(&url)
+            .set("Cookie", &cookie)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| ClientError::Http(url.clone(), Box::new(e)))?
+            .into_string()
+            .map_err(|e| ClientError::Body(url.clone(), e))?;
+        parse_verdict(&body).ok_or(ClientError::UnrecognisedResponse(url))
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// No session token has been configured
+    MissingSession,
+    /// The puzzle will be released at the given time
+    NotReleased(DateTime<Utc>),
+    Http(String, Box<ureq::Error>),
+    Body(String, io::Error),
+    UnrecognisedResponse(String),
+    Io(PathBuf, io::Error),
+}
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSession => write!(
+                f,
+                "No session token has been configured. Set `session` in {} or the {} environment variable",
+                crate::config::Config::path().display(),
+                crate::config::SESSION_VAR
+            ),
+            Self::NotReleased(time) => write!(f, "The puzzle will not be released until {}", time),
+            Self::Http(url, e) => write!(f, "Request to {} failed: {}", url, e),
+            Self::Body(url, e) => write!(f, "Failed to read the response from {}: {}", url, e),
+            Self::UnrecognisedResponse(url) => {
+                write!(f, "Unrecognised response from {}", url)
+            }
+            Self::Io(path, e) => write!(f, "Failed to write \"{}\": {}", path.display(), e),
+        }
+    }
+}
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Http(_, e) => Some(e.as_ref()),
+            Self::Body(_, e) | Self::Io(_, e) => Some(e),
+            Self::MissingSession | Self::NotReleased(_) | Self::UnrecognisedResponse(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    /// Serve one request for each of `bodies` in turn, returning the raw requests received
+    fn mock_server(bodies: &'static [&'static str]) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            bodies
+                .iter()
+                .map(|body| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    let mut request = String::new();
+                    let mut length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some((name, value)) = line.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                length = value.trim().parse().unwrap();
+                            }
+                        }
+                        request.push_str(&line);
+                        if line == "\r\n" {
+                            break;
+                        }
+                    }
+                    let mut content = vec![0; length];
+                    reader.read_exact(&mut content).unwrap();
+                    request.push_str(&String::from_utf8(content).unwrap());
+                    write!(
+                        reader.get_mut(),
+                        "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                    .unwrap();
+                    request
+                })
+                .collect()
+        });
+        (url, handle)
+    }
+
+    fn client(base_url: String) -> Client {
+        Client::new(&Config {
+            session: Some("abc123".to_string()),
+            base_url,
+            request_interval_ms: 0,
+            ..Config::default()
+        })
+    }
+
+    #[test]
+    fn parse_verdicts() {
+        let cases = [
+            ("<p>That's the right answer! You are one gold star closer", Verdict::Correct),
+            ("<p>That's not the right answer; your answer is too high.  If you're stuck", Verdict::TooHigh),
+            ("<p>That's not the right answer; your answer is too low.", Verdict::TooLow),
+            ("<p>That's not the right answer.  If you're stuck", Verdict::Incorrect),
+            (
+                "<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.",
+                Verdict::RateLimited { wait_secs: Some(65) },
+            ),
+            (
+                "<p>You gave an answer too recently. You have 37s left to wait.",
+                Verdict::RateLimited { wait_secs: Some(37) },
+            ),
+            ("<p>You don't seem to be solving the right level.  Did you already complete it?", Verdict::WrongLevel),
+        ];
+        for (body, verdict) in cases {
+            assert_eq!(parse_verdict(body), Some(verdict), "{}", body);
+        }
+        assert_eq!(parse_verdict("<html>Something else</html>"), None);
+    }
+
+    #[test]
+    fn submit_to_mock_server() {
+        let (url, server) = mock_server(&["<article><p>That's the right answer!</p></article>"]);
+        let verdict = client(url).submit(2023, 6, 1, "288").unwrap();
+        let request = &server.join().unwrap()[0];
+        assert_eq!(verdict, Verdict::Correct);
+        assert!(
+            request.starts_with("POST /2023/day/6/answer HTTP/1.1\r\n"),
+            "{}",
+            request
+        );
+        assert!(
+            request.contains("Cookie: session=abc123\r\n"),
+            "{}",
+            request
+        );
+        assert!(
+            request.contains(&format!("User-Agent: {}\r\n", USER_AGENT)),
+            "{}",
+            request
+        );
+        assert!(
+            request.ends_with("\r\n\r\nlevel=1&answer=288"),
+            "{}",
+            request
+        );
+    }
+
+    #[test]
+    fn submit_without_session() {
+        let client = Client::new(&Config::default());
+        assert!(matches!(
+            client.submit(2023, 6, 1, "288"),
+            Err(ClientError::MissingSession)
+        ));
+    }
+
+    #[test]
+    fn download_input_from_mock_server() {
+        let (url, server) = mock_server(&["1 2 3\n"]);
+        let dir = std::env::temp_dir().join(format!("aoc-download-{}", std::process::id()));
+        let path = dir.join("2023").join("06.txt");
+        let _ = fs::remove_dir_all(&dir);
+        let client = client(url);
+        assert!(client.download_input(2023, 6, &path).unwrap());
+        let request = &server.join().unwrap()[0];
+        assert!(
+            request.starts_with("GET /2023/day/6/input HTTP/1.1\r\n"),
+            "{}",
+            request
+        );
+        assert!(
+            request.contains("Cookie: session=abc123\r\n"),
+            "{}",
+            request
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2 3\n");
+        // The server has stopped, so this would fail if it tried to download the input again
+        assert!(!client.download_input(2023, 6, &path).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn requests_are_throttled() {
+        let (url, server) = mock_server(&["first", "second"]);
+        let client = Client::new(&Config {
+            session: Some("abc123".to_string()),
+            base_url: url,
+            request_interval_ms: 200,
+            ..Config::default()
+        });
+        let start = Instant::now();
+        assert_eq!(client.fetch_input(2023, 1).unwrap(), "first");
+        assert_eq!(client.fetch_input(2023, 2).unwrap(), "second");
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn unreleased_puzzles_are_not_fetched() {
+        let client = client("http://127.0.0.1:9".to_string());
+        let year = (Utc::now().format("%Y").to_string().parse::<u16>().unwrap()) + 1;
+        assert!(matches!(
+            client.fetch_input(year, 1),
+            Err(ClientError::NotReleased(_))
+        ));
+    }
+}
@@ -0,0 +1,109 @@
+use std::{env, fmt, fs, io, path::PathBuf};
+
+use serde::Deserialize;
+
+/// The environment variable which can be used to override the config file location
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+/// The environment variable which overrides the `session` config value
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// The environment variable which overrides the `base_url` config value
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+/// The environment variable which overrides the `history_dir` config value
+pub const HISTORY_DIR_VAR: &str = "AOC_HISTORY_DIR";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings for talking to the Advent of Code server, read from `aoc.toml` in the root of this
+/// repository. The session, server and history directory can be overridden with environment variables
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The value of the `session` cookie from a logged in browser
+    pub session: Option<String>,
+    /// The server to send requests to, without a trailing slash
+    pub base_url: String,
+    /// Where the answer history is kept, laid out as `<dir>/<yyyy>/<dd>.jsonl`
+    pub history_dir: PathBuf,
+    /// Sent with every request, so the server knows who to contact about misbehaving tools
+    pub user_agent: String,
+    /// The minimum time between requests to the server
+    pub request_interval_ms: u64,
+    /// Download missing inputs when running a solution, if a session has been configured
+    pub auto_fetch: bool,
+}
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            history_dir: PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../history")),
+            user_agent: crate::client::USER_AGENT.to_string(),
+            request_interval_ms: 3000,
+            auto_fetch: true,
+        }
+    }
+}
+impl Config {
+    /// The config file path: the value of `AOC_CONFIG` if set, otherwise `aoc.toml` in the root
+    /// of this repository
+    pub fn path() -> PathBuf {
+        env::var_os(CONFIG_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/aoc.toml")))
+    }
+    /// Read the config file (if it exists), then apply the environment variable overrides
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = Self::path();
+        let mut config = match fs::read_to_string(&path) {
+            Ok(s) => toml::from_str(&s).map_err(|e| ConfigError::Parse(path, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(ConfigError::Read(path, e)),
+        };
+        if let Some(session) = env::var_os(SESSION_VAR) {
+            config.session = Some(session.to_string_lossy().into_owned());
+        }
+        if let Some(url) = env::var_os(BASE_URL_VAR) {
+            config.base_url = url.to_string_lossy().into_owned();
+        }
+        if let Some(dir) = env::var_os(HISTORY_DIR_VAR) {
+            config.history_dir = dir.into();
+        }
+        config.base_url = config.base_url.trim_end_matches('/').to_string();
+        config.session = config
+            .session
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        Ok(config)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+}
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(path, e) => {
+                write!(
+                    f,
+                    "Failed to read config file \"{}\": {}",
+                    path.display(),
+                    e
+                )
+            }
+            Self::Parse(path, e) => {
+                write!(f, "Invalid config file \"{}\": {}", path.display(), e)
+            }
+        }
+    }
+}
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Read(_, e) => Some(e),
+            Self::Parse(_, e) => Some(e),
+        }
+    }
+}
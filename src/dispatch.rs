@@ -1,241 +1,114 @@
-#![allow(dead_code)]
-
-use std::{fmt::Display, time::Instant};
+/// Generate the `main` function for a day module from its functions, using [`crate::SolutionBuilder`],
+/// along with the `PARTS` constant used by the registry, which is taken from the type of the
+/// built solution. Use `try_setup:` for a setup function which returns a `Result`.
+/// Days with parameters (see [`crate::Param`]) or fallible parts build the solution themselves,
+/// from the parameter values:
+/// ```ignore
+/// crate::aoc! {
+///     solution: |params| {
+///         use crate::dispatcher::*;
+///         let size = SIZE.get::<usize>(params);
+///         DispatcherBuilder::setup(setup)
+///             .part1(part1)
+///             .try_part2(move |data| size.clone().map(|size| part2(data, size)))
+///     },
+/// }
+/// ```
+/// `TITLE`, `EXAMPLES` and `PARAMS` default to empty for days which do not declare them, so the
+/// registry can refer to them for every day
+#[macro_export]
+macro_rules! aoc {
+    (
+        $(#[$attr:meta])*
+        solution: |$params:ident| $solution:expr $(,)?
+    ) => {
+        $(#[$attr])*
+        fn solution<'s>($params: &$crate::Params) -> impl $crate::Solution<'s> {
+            $solution
+        }
+        pub const PARTS: u8 = $crate::solution::parts(solution);
+        pub fn main(
+            input: &str,
+            parts: $crate::RunPart,
+            params: &$crate::Params,
+        ) -> $crate::RunReport {
+            use $crate::Solution;
+            solution(params).run(input, parts)
+        }
 
-const EMPTY_STR: String = String::new();
-macro_rules! format_setup_duration {
-    ($s:literal, $d:expr) => {
-        if Self::has_setup() {
-            format!($s, $d)
-        } else {
-            EMPTY_STR
+        /// Used for any of these which the day does not declare itself, as a declared item
+        /// shadows a glob import
+        #[doc(hidden)]
+        pub mod defaults {
+            pub const TITLE: &str = "";
+            pub const EXAMPLES: &[$crate::Example] = &[];
+            pub const PARAMS: &[$crate::Param] = &[];
         }
+        #[allow(unused_imports)]
+        pub use self::defaults::*;
     };
-}
-
-pub(crate) trait AocSolutions<'s, C, S1, S2 = &'static str>
-where
-    S1: Display,
-    S2: Display,
-{
-    fn setup(&self, input: &'s str) -> C;
-    fn has_setup() -> bool {
-        true
-    }
-    fn part1(&self, input: &C) -> S1;
-    fn part2(&self, input: C) -> S2;
-    fn run(&self, input: &'s str, parts: crate::RunPart) {
-        let now = Instant::now();
-        let setup = self.setup(input);
-        let d = now.elapsed();
-        if Self::has_setup() {
-            println!("Setup took {:?}", d)
-        };
-        match parts {
-            crate::RunPart::All => {
-                let i1 = Instant::now();
-                let p1 = self.part1(&setup);
-                let d1 = i1.elapsed();
-                println!(
-                    "Part 1: {}. Completed in {:?}{}",
-                    p1,
-                    d1,
-                    format_setup_duration!(" ({:?} including setup)", d + d1),
-                );
-                let i2 = Instant::now();
-                let p2 = self.part2(setup);
-                let d2 = i2.elapsed();
-                println!(
-                    "Part 2: {}. Completed in {:?}{}",
-                    p2,
-                    d2,
-                    format_setup_duration!(" ({:?} including setup)", d + d2),
-                );
-            }
-            crate::RunPart::Part1 => {
-                let i1 = Instant::now();
-                let p1 = self.part1(&setup);
-                let d1 = i1.elapsed();
-                println!(
-                    "Part 1: {}. Completed in {:?}{}",
-                    p1,
-                    d1,
-                    format_setup_duration!(" ({:?} including setup)", d + d1),
-                );
-            }
-            crate::RunPart::Part2 => {
-                let i2 = Instant::now();
-                let p2 = self.part2(setup);
-                let d2 = i2.elapsed();
-                println!(
-                    "Part 2: {}. Completed in {:?}{}",
-                    p2,
-                    d2,
-                    format_setup_duration!(" ({:?} including setup)", d + d2),
-                );
+    (
+        try_setup: $setup:expr,
+        part1: $f1:expr $(,)?
+    ) => {
+        $crate::aoc! {
+            solution: |_params| $crate::SolutionBuilder::try_setup($setup).part1($f1)
+        }
+    };
+    (
+        try_setup: $setup:expr,
+        part1: $f1:expr,
+        part2: $f2:expr $(,)?
+    ) => {
+        $crate::aoc! {
+            solution: |_params| {
+                $crate::SolutionBuilder::try_setup($setup)
+                    .part1($f1)
+                    .part2($f2)
             }
         }
-    }
-}
-
-pub(crate) struct SolutionP1<'s, S1>(fn(&'s str) -> S1)
-where
-    S1: Display;
-impl<'s, S1> SolutionP1<'s, S1>
-where
-    S1: Display,
-{
-    pub fn new(p1: fn(&'s str) -> S1) -> Self {
-        Self(p1)
-    }
-}
-impl<'s, S1> AocSolutions<'s, &'s str, S1> for SolutionP1<'s, S1>
-where
-    S1: Display,
-{
-    fn setup(&self, input: &'s str) -> &'s str {
-        input
-    }
-    fn has_setup() -> bool {
-        false
-    }
-    fn part1(&self, input: &&'s str) -> S1 {
-        self.0(*input)
-    }
-    fn part2(&self, _input: &'s str) -> &'static str {
-        "Not yet implemented"
-    }
-}
-pub(crate) struct SolutionP1S<'s, C, S1>(fn(&'s str) -> C, fn(&C) -> S1)
-where
-    S1: Display;
-impl<'s, C, S1> SolutionP1S<'s, C, S1>
-where
-    S1: Display,
-{
-    pub fn new(setup: fn(&'s str) -> C, p1: fn(&C) -> S1) -> Self {
-        Self(setup, p1)
-    }
-}
-impl<'s, C, S1> AocSolutions<'s, C, S1> for SolutionP1S<'s, C, S1>
-where
-    S1: Display,
-{
-    fn setup(&self, input: &'s str) -> C {
-        self.0(input)
-    }
-    fn part1(&self, input: &C) -> S1 {
-        self.1(input)
-    }
-    fn part2(&self, _input: C) -> &'static str {
-        "Not yet implemented"
-    }
-}
-pub(crate) struct SolutionP2<'s, S1, S2>(fn(&'s str) -> S1, fn(&'s str) -> S2)
-where
-    S1: Display,
-    S2: Display;
-impl<'s, S1, S2> SolutionP2<'s, S1, S2>
-where
-    S1: Display,
-    S2: Display,
-{
-    pub fn new(p1: fn(&'s str) -> S1, p2: fn(&'s str) -> S2) -> Self {
-        Self(p1, p2)
-    }
-}
-impl<'s, S1, S2> AocSolutions<'s, &'s str, S1, S2> for SolutionP2<'s, S1, S2>
-where
-    S1: Display,
-    S2: Display,
-{
-    fn setup(&self, input: &'s str) -> &'s str {
-        input
-    }
-    fn has_setup() -> bool {
-        false
-    }
-    fn part1(&self, input: &&'s str) -> S1 {
-        self.0(*input)
-    }
-    fn part2(&self, input: &'s str) -> S2 {
-        self.1(input)
-    }
-}
-pub(crate) struct SolutionP2S<'s, C, S1, S2>(fn(&'s str) -> C, fn(&C) -> S1, fn(C) -> S2)
-where
-    S1: Display,
-    S2: Display;
-impl<'s, C, S1, S2> SolutionP2S<'s, C, S1, S2>
-where
-    S1: Display,
-    S2: Display,
-{
-    pub fn new(setup: fn(&'s str) -> C, p1: fn(&C) -> S1, p2: fn(C) -> S2) -> Self {
-        Self(setup, p1, p2)
-    }
-}
-impl<'s, C, S1, S2> AocSolutions<'s, C, S1, S2> for SolutionP2S<'s, C, S1, S2>
-where
-    S1: Display,
-    S2: Display,
-{
-    fn setup(&self, input: &'s str) -> C {
-        self.0(input)
-    }
-    fn part1(&self, input: &C) -> S1 {
-        self.1(input)
-    }
-    fn part2(&self, input: C) -> S2 {
-        self.2(input)
-    }
-}
-#[macro_export]
-macro_rules! aoc {
+    };
     (
-        $input:expr,
-        $f1:expr $(,)?
+        setup: $setup:expr,
+        part1: $f1:expr $(,)?
     ) => {
-        pub(super) fn main(parts: crate::RunPart) {
-            use crate::AocSolutions;
-            crate::SolutionP1::new($f1).run($input, parts);
+        $crate::aoc! {
+            solution: |_params| $crate::SolutionBuilder::setup($setup).part1($f1)
         }
     };
     (
-        $input:expr,
         setup: $setup:expr,
-        part1: $f1:expr$(,)?
+        part1: $f1:expr,
+        part2: $f2:expr $(,)?
+    ) => {
+        $crate::aoc!($setup, $f1, $f2);
+    };
+    (
+        $f1:expr $(,)?
     ) => {
-        pub(super) fn main(parts: crate::RunPart) {
-            use crate::AocSolutions;
-            crate::SolutionP1S::new($setup, $f1).run($input, parts);
+        $crate::aoc! {
+            solution: |_params| $crate::SolutionBuilder::part1($f1)
         }
     };
     (
-        $input:expr,
         $f1:expr,
         $f2:expr $(,)?
     ) => {
-        pub(super) fn main(parts: crate::RunPart) {
-            use crate::AocSolutions;
-            crate::SolutionP2::new($f1, $f2).run($input, parts);
-        }
-    };
-    ($input:expr, setup, part1, part2 $(,)?) => {
-        pub(super) fn main(parts: crate::RunPart) {
-            use crate::AocSolutions;
-            crate::SolutionP2S::new(setup, part1, part2).run($input, parts);
+        $crate::aoc! {
+            solution: |_params| $crate::SolutionBuilder::part1($f1).part2($f2)
         }
     };
     (
-        $input:expr,
-        $(setup:)? $setup:expr,
-        $(part1:)? $f1:expr,
-        $(part2:)? $f2:expr $(,)?
+        $setup:expr,
+        $f1:expr,
+        $f2:expr $(,)?
     ) => {
-        pub(super) fn main(parts: crate::RunPart) {
-            use crate::AocSolutions;
-            crate::SolutionP2S::new($setup, $f1, $f2).run($input, parts);
+        $crate::aoc! {
+            solution: |_params| {
+                $crate::SolutionBuilder::setup($setup)
+                    .part1($f1)
+                    .part2($f2)
+            }
         }
     };
 }
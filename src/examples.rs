@@ -0,0 +1,143 @@
+use crate::{Params, RunPart, RunReport};
+
+/// An example input from the puzzle description, along with the expected answers (if known)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    pub name: &'static str,
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+    /// Parameter values which differ from the defaults for the real input, as `(name, value)`
+    pub params: &'static [(&'static str, &'static str)],
+}
+impl Example {
+    pub fn params(&self) -> Params {
+        self.params.iter().copied().collect()
+    }
+    /// The parts which have an expected answer. Examples with no expected answers run both parts
+    pub fn parts(&self) -> RunPart {
+        match (self.part1, self.part2) {
+            (Some(_), None) => RunPart::Part1,
+            (None, Some(_)) => RunPart::Part2,
+            _ => RunPart::All,
+        }
+    }
+    /// The parts to run for this example, when `requested` parts were asked for.
+    /// Returns `None` if there is no overlap
+    pub fn parts_for(&self, requested: RunPart) -> Option<RunPart> {
+        match (self.parts(), requested) {
+            (RunPart::All, p) | (p, RunPart::All) => Some(p),
+            (a, b) if a == b => Some(a),
+            _ => None,
+        }
+    }
+    /// Compare the answers of a run against the expected answers, for the parts which the run
+    /// requested. Returns a list of `(part, expected, actual)` for each part which did not match.
+    /// Surrounding whitespace is ignored
+    pub fn check(&self, report: &RunReport) -> Vec<(u8, &'static str, Option<String>)> {
+        [
+            (1, self.part1.filter(|_| report.parts.run_p1())),
+            (2, self.part2.filter(|_| report.parts.run_p2())),
+        ]
+        .into_iter()
+        .filter_map(|(part, expected)| {
+            let expected = expected?;
+            let actual = report.answer(part);
+            if actual.map(str::trim) == Some(expected.trim()) {
+                None
+            } else {
+                Some((part, expected, actual.map(String::from)))
+            }
+        })
+        .collect()
+    }
+}
+
+/// Run every example with an expected answer through `main`, panicking if any of them do not
+/// produce the expected answers
+#[cfg(test)]
+pub fn check_all(examples: &[Example], main: fn(&str, RunPart, &Params) -> RunReport) {
+    let failures = examples
+        .iter()
+        // Nothing to check (e.g. a newly added day)
+        .filter(|example| example.part1.is_some() || example.part2.is_some())
+        .flat_map(|example| {
+            let report = main(example.input, example.parts(), &example.params());
+            let failure = report
+                .failure
+                .as_ref()
+                .map(|failure| format!("Example \"{}\" failed. {}", example.name, failure));
+            example
+                .check(&report)
+                .into_iter()
+                .map(move |(part, expected, actual)| {
+                    format!(
+                        "Example \"{}\" part {}: expected {}, got {:?}",
+                        example.name, part, expected, actual
+                    )
+                })
+                .chain(failure)
+        })
+        .collect::<Vec<_>>();
+    if !failures.is_empty() {
+        panic!("{}", failures.join("\n"));
+    }
+}
+
+/// Declare the example inputs for a day, along with the expected answer for each part (`_` if unknown),
+/// and any parameters which need different values for the example.
+/// Also generates a test which checks each example against the day's `main` function.
+/// ```ignore
+/// crate::examples! {
+///     "example" => r"..." => (157, 70),
+///     "part 1 only" => TEST_INPUT => (13, _),
+///     "small grid" => TEST_INPUT => (26, 56000011) with { row = 10, size = 20 },
+/// }
+/// ```
+#[macro_export]
+macro_rules! examples {
+    ($($name:literal => $input:expr => ($p1:tt, $p2:tt) $(with { $($param:ident = $value:literal),* $(,)? })?),+ $(,)?) => {
+        pub const EXAMPLES: &[$crate::Example] = &[
+            $(
+                $crate::Example {
+                    name: $name,
+                    input: $input,
+                    part1: $crate::examples!(@answer $p1),
+                    part2: $crate::examples!(@answer $p2),
+                    params: &[$($((stringify!($param), concat!($value)),)*)?],
+                },
+            )+
+        ];
+
+        #[cfg(test)]
+        #[test]
+        fn examples() {
+            $crate::examples::check_all(EXAMPLES, main);
+        }
+    };
+    (@answer _) => {
+        None
+    };
+    (@answer $answer:literal) => {
+        Some(concat!($answer))
+    };
+}
+
+#[cfg(all(test, feature = "y2023"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_selected_parts() {
+        let info = crate::solution(2023, 5).unwrap();
+        let example = info.examples[0];
+        assert!(example.part1.is_some() && example.part2.is_some());
+        let report = info.run_with(example.input, RunPart::Part2, &example.params());
+        assert!(report.part1.is_none());
+        assert_eq!(example.check(&report), []);
+
+        let mut report = info.run_with(example.input, RunPart::All, &example.params());
+        report.part1 = None;
+        assert_eq!(example.check(&report), [(1, "35", None)]);
+    }
+}
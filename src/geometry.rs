@@ -0,0 +1,284 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// A 2D point. `y` increases downwards, matching the layout of the puzzle inputs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+/// The difference between two points
+pub type Vector<T> = Point<T>;
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Point<U> {
+        Point::new(f(self.x), f(self.y))
+    }
+}
+impl<T: Copy + Ord + Sub<Output = T> + Add<Output = T>> Point<T> {
+    /// The distance moving only orthogonally
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+    /// The distance moving orthogonally or diagonally
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+/// Works for unsigned types, unlike `(a - b).abs()`
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl Point<usize> {
+    /// The point moved by `offset`, or `None` if either coordinate would be negative
+    pub fn checked_add_signed(self, offset: Vector<isize>) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add_signed(offset.x)?,
+            self.y.checked_add_signed(offset.y)?,
+        ))
+    }
+    /// The point one step away in the direction, or `None` if either coordinate would be negative
+    pub fn checked_step(self, dir: impl Into<Dir8>) -> Option<Self> {
+        self.checked_add_signed(dir.into().offset())
+    }
+    /// The orthogonal neighbours which have no negative coordinates, clockwise from up
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |d| self.checked_step(d))
+    }
+    /// The orthogonal and diagonal neighbours which have no negative coordinates, clockwise from up
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |d| self.checked_step(d))
+    }
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),+) => {$(
+        impl Point<$t> {
+            /// Each coordinate replaced by -1, 0 or 1. A step of at most one in each direction
+            /// towards the vector
+            pub fn signum(self) -> Self {
+                self.map(<$t>::signum)
+            }
+            /// The point one step away in the direction
+            pub fn step(self, dir: impl Into<Dir8>) -> Self {
+                self + dir.into().offset()
+            }
+        }
+    )+};
+}
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+/// Scale by a scalar
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+impl<T> From<Point<T>> for (T, T) {
+    fn from(p: Point<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// An orthogonal direction. Up is towards negative `y`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+impl Dir4 {
+    /// Clockwise from up
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+    /// A quarter turn clockwise
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+    /// A quarter turn anticlockwise
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+    /// The vector of a single step in this direction
+    pub fn offset<T: From<i8>>(self) -> Vector<T> {
+        Dir8::from(self).offset()
+    }
+}
+
+/// An orthogonal or diagonal direction. Up is towards negative `y`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+impl Dir8 {
+    /// Clockwise from up
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+    /// An eighth of a turn clockwise
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+    /// An eighth of a turn anticlockwise
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+    /// The vector of a single step in this direction
+    pub fn offset<T: From<i8>>(self) -> Vector<T> {
+        let (x, y) = match self {
+            Self::Up => (0, -1),
+            Self::UpRight => (1, -1),
+            Self::Right => (1, 0),
+            Self::DownRight => (1, 1),
+            Self::Down => (0, 1),
+            Self::DownLeft => (-1, 1),
+            Self::Left => (-1, 0),
+            Self::UpLeft => (-1, -1),
+        };
+        Point::new(x.into(), y.into())
+    }
+    /// The direction of a vector with each coordinate -1, 0 or 1 (see [`Point::signum`]).
+    /// `None` for the zero vector
+    pub fn from_offset<T: Into<i128>>(offset: Vector<T>) -> Option<Self> {
+        let offset = offset.map(Into::into);
+        Self::ALL.into_iter().find(|d| d.offset::<i128>() == offset)
+    }
+}
+impl From<Dir4> for Dir8 {
+    fn from(d: Dir4) -> Self {
+        Self::ALL[d as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_and_distance() {
+        let a = Point::new(1i32, 5);
+        let b = Point::new(4, -1);
+        assert_eq!(a + b, Point::new(5, 4));
+        assert_eq!(a - b, Point::new(-3, 6));
+        assert_eq!(-a * 2, Point::new(-2, -10));
+        assert_eq!((a - b).signum(), Point::new(-1, 1));
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(Point::new(3usize, 0).manhattan(Point::new(1, 2)), 4);
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+        assert_eq!(Dir8::UpLeft.turn_right(), Dir8::Up);
+        assert_eq!(Dir8::DownRight.opposite(), Dir8::UpLeft);
+        assert_eq!(Dir8::from(Dir4::Down), Dir8::Down);
+        assert!(Dir8::DownLeft.is_diagonal());
+        assert_eq!(Dir4::Left.offset::<i64>(), Point::new(-1, 0));
+        for d in Dir8::ALL {
+            assert_eq!(Dir8::from_offset(d.offset::<i16>()), Some(d));
+            assert_eq!(d.offset::<i32>() + d.opposite().offset(), Point::default());
+        }
+        assert_eq!(Dir8::from_offset(Point::new(0u8, 0)), None);
+        assert_eq!(Point::new(2i16, 2).step(Dir4::Up), Point::new(2, 1));
+    }
+
+    #[test]
+    fn unsigned_neighbours() {
+        let origin = Point::new(0usize, 0);
+        assert_eq!(origin.checked_step(Dir4::Left), None);
+        assert_eq!(origin.checked_step(Dir8::DownRight), Some(Point::new(1, 1)));
+        assert_eq!(
+            origin.neighbours4().collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(origin.neighbours8().count(), 3);
+        assert_eq!(Point::new(1usize, 1).neighbours8().count(), 8);
+    }
+}
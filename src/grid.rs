@@ -0,0 +1,360 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::geometry::Point;
+
+/// A rectangular grid of cells, stored row by row. Positions are `(x, y)`: the column, then the
+/// row, with `(0, 0)` at the top left
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+impl<T> Grid<T> {
+    /// A grid with every cell set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+    /// A grid with each cell set to the result of `f` for its position
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point<usize>) -> T) -> Self {
+        Self {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+                .map(&mut f)
+                .collect(),
+        }
+    }
+    /// Build a grid from its rows, which must all be the same length
+    pub fn from_rows<R>(rows: impl IntoIterator<Item = R>) -> Result<Self, GridError>
+    where
+        R: IntoIterator<Item = T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for row in rows {
+            let len = cells.len();
+            cells.extend(row);
+            let row_width = cells.len() - len;
+            match width {
+                None => width = Some(row_width),
+                Some(w) if w != row_width => {
+                    return Err(GridError::Ragged {
+                        row: height,
+                        expected: w,
+                        found: row_width,
+                    })
+                }
+                _ => (),
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+    /// Parse a grid with one row per line, converting each character with `f`.
+    /// Blank lines at the start and end of the input are ignored
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        Self::parse_cells(input, |c, _| Ok(f(c)))
+    }
+    /// Parse a grid with one row per line, converting each character with `f`, which returns
+    /// `None` for characters which are not allowed
+    pub fn parse_with(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, GridError> {
+        Self::parse_cells(input, |c, pos| {
+            f(c).ok_or(GridError::InvalidChar { c, pos })
+        })
+    }
+    fn parse_cells(
+        input: &str,
+        mut f: impl FnMut(char, Point<usize>) -> Result<T, GridError>,
+    ) -> Result<Self, GridError> {
+        let rows = input
+            .trim_matches(['\n', '\r'])
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.trim_end_matches('\r')
+                    .chars()
+                    .enumerate()
+                    .map(|(x, c)| f(c, Point::new(x, y)))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn in_bounds(&self, pos: Point<usize>) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+    /// `None` if the position is outside the grid
+    pub fn get(&self, pos: Point<usize>) -> Option<&T> {
+        self.in_bounds(pos)
+            .then(|| &self.cells[pos.y * self.width + pos.x])
+    }
+    pub fn get_mut(&mut self, pos: Point<usize>) -> Option<&mut T> {
+        if self.in_bounds(pos) {
+            Some(&mut self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+    /// The position of the first cell (in row order) matching the predicate
+    pub fn position(&self, pred: impl FnMut(&T) -> bool) -> Option<Point<usize>> {
+        self.cells
+            .iter()
+            .position(pred)
+            .map(|i| Point::new(i % self.width, i / self.width))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + '_ {
+        assert!(x < self.width, "column {} is outside the grid", x);
+        self.cells[x..].iter().step_by(self.width)
+    }
+    pub fn columns(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T> + ExactSizeIterator>
+           + ExactSizeIterator {
+        (0..self.width).map(|x| self.column(x))
+    }
+    /// Every position, in row order
+    pub fn positions(&self) -> impl Iterator<Item = Point<usize>> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+    /// Every cell along with its position, in row order
+    pub fn cells(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = (Point<usize>, &mut T)> {
+        self.positions().zip(self.cells.iter_mut())
+    }
+
+    /// The orthogonal neighbours which are inside the grid, clockwise from up
+    pub fn neighbours4(&self, pos: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        pos.neighbours4().filter(|&p| self.in_bounds(p))
+    }
+    /// The orthogonal and diagonal neighbours which are inside the grid, clockwise from up
+    pub fn neighbours8(&self, pos: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        pos.neighbours8().filter(|&p| self.in_bounds(p))
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.into_iter().map(f).collect(),
+        }
+    }
+    /// Swap the rows and columns
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, p.x)].clone()
+        })
+    }
+    /// Rotate a quarter turn clockwise
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, self.height - 1 - p.x)].clone()
+        })
+    }
+    /// Rotate a quarter turn anticlockwise
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(self.width - 1 - p.y, p.x)].clone()
+        })
+    }
+}
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    /// Panics if the position is outside the grid
+    fn index(&self, pos: Point<usize>) -> &T {
+        let (width, height) = (self.width, self.height);
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "{} is outside the grid (width = {}, height = {})",
+                pos, width, height
+            )
+        })
+    }
+}
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, pos: Point<usize>) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| {
+            panic!(
+                "{} is outside the grid (width = {}, height = {})",
+                pos, width, height
+            )
+        })
+    }
+}
+/// Each cell is written with no separator, with one row per line
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// A row which is a different length to the first row
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidChar {
+        c: char,
+        pos: Point<usize>,
+    },
+}
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Row {} has {} cells, but the first row has {}",
+                row, found, expected
+            ),
+            Self::InvalidChar { c, pos } => {
+                write!(
+                    f,
+                    "Invalid character {:?} at row {}, column {}",
+                    c, pos.y, pos.x
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for GridError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::parse("\n123\n456\n", |c| c.to_digit(10).unwrap() as u8).unwrap()
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 0)], 3);
+        assert_eq!(grid.get(Point::new(0, 1)), Some(&4));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+        assert_eq!(grid.position(|&c| c == 5), Some(Point::new(1, 1)));
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(
+            Grid::parse("12\n3", |c| c),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::parse_with("..\n.x", |c| (c == '.').then_some(())),
+            Err(GridError::InvalidChar {
+                c: 'x',
+                pos: Point::new(1, 1)
+            })
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            [[1, 4], [2, 5], [3, 6]]
+        );
+        assert_eq!(grid.column(1).rev().copied().collect::<Vec<_>>(), [5, 2]);
+        assert_eq!(
+            grid.cells().nth(4),
+            Some((Point::new(1, 1), &5)),
+            "cells are in row order"
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 3, ());
+        assert_eq!(
+            grid.neighbours4(Point::new(0, 0)).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbours8(Point::new(2, 2)).collect::<Vec<_>>(),
+            [Point::new(2, 1), Point::new(1, 2), Point::new(1, 1)]
+        );
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn transform() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.rotate_cw().to_string(), "41\n52\n63");
+        assert_eq!(grid.rotate_ccw().to_string(), "36\n25\n14");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.map(|c| c * 2).to_string(), "246\n81012");
+    }
+}
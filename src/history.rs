@@ -0,0 +1,302 @@
+use std::{
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::client::Verdict;
+
+/// A single answer recorded in the history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// When the answer was recorded, in RFC 3339 format
+    pub timestamp: String,
+    pub part: u8,
+    pub answer: String,
+    pub submitted: bool,
+    /// The server's response, if the answer was submitted
+    pub verdict: Option<Verdict>,
+}
+impl Entry {
+    pub fn new(part: u8, answer: &str, verdict: Option<Verdict>) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339(),
+            part,
+            answer: answer.to_string(),
+            submitted: verdict.is_some(),
+            verdict,
+        }
+    }
+}
+
+/// The answers recorded for a single day, stored as one JSON object per line in
+/// `<history dir>/<yyyy>/<dd>.jsonl`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    path: PathBuf,
+    pub entries: Vec<Entry>,
+}
+impl History {
+    /// Load the history for a day. A missing file is an empty history
+    pub fn load(dir: &Path, year: u16, day: u8) -> Result<Self, HistoryError> {
+        let path = dir.join(year.to_string()).join(format!("{:02}.jsonl", day));
+        let entries = match fs::read_to_string(&path) {
+            Ok(s) => s
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(i, line)| {
+                    serde_json::from_str(line)
+                        .map_err(|e| HistoryError::Parse(path.clone(), i + 1, e))
+                })
+                .collect::<Result<_, _>>()?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(HistoryError::Io(path, e)),
+        };
+        Ok(Self { path, entries })
+    }
+    /// Append an entry, writing it to the file
+    pub fn record(&mut self, entry: Entry) -> Result<(), HistoryError> {
+        let line = serde_json::to_string(&entry).unwrap();
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|e| HistoryError::Io(parent.into(), e))?;
+        }
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut f| writeln!(f, "{}", line))
+            .map_err(|e| HistoryError::Io(self.path.clone(), e))?;
+        self.entries.push(entry);
+        Ok(())
+    }
+    /// The answer which the server confirmed as correct for the part, if any
+    pub fn correct(&self, part: u8) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.part == part && e.verdict == Some(Verdict::Correct))
+            .map(|e| e.answer.as_str())
+    }
+    /// Record an answer produced by a run, unless it has already been recorded for the part
+    pub fn record_answer(&mut self, part: u8, answer: &str) -> Result<(), HistoryError> {
+        if self
+            .entries
+            .iter()
+            .any(|e| e.part == part && e.answer == answer)
+        {
+            return Ok(());
+        }
+        self.record(Entry::new(part, answer, None))
+    }
+    /// The bounds on the answer for the part, from the guesses which were too low and too high.
+    /// Only integer answers are considered
+    pub fn bounds(&self, part: u8) -> Bounds {
+        let guesses = |verdict: Verdict| {
+            self.entries
+                .iter()
+                .filter(move |e| e.part == part && e.verdict.as_ref() == Some(&verdict))
+                .filter_map(|e| e.answer.parse::<i128>().ok())
+        };
+        Bounds {
+            low: guesses(Verdict::TooLow).max(),
+            high: guesses(Verdict::TooHigh).min(),
+        }
+    }
+    /// What is known about an answer to the part
+    pub fn status(&self, part: u8, answer: &str) -> AnswerStatus {
+        if let Some(correct) = self.correct(part) {
+            return if correct == answer {
+                AnswerStatus::Accepted
+            } else {
+                AnswerStatus::Mismatch(correct.to_string())
+            };
+        }
+        if let Some(verdict) = self.verdict(part, answer).filter(|v| v.is_wrong()) {
+            return AnswerStatus::Rejected(verdict.clone());
+        }
+        if let Ok(n) = answer.parse::<i128>() {
+            let bounds = self.bounds(part);
+            if !bounds.contains(n) {
+                return AnswerStatus::OutOfBounds(bounds);
+            }
+        }
+        AnswerStatus::Unknown
+    }
+    /// The verdict the server gave the answer when it was last checked.
+    /// Rate limited submissions were not checked, so are ignored
+    pub fn verdict(&self, part: u8, answer: &str) -> Option<&Verdict> {
+        self.entries
+            .iter()
+            .rev()
+            .filter(|e| e.part == part && e.answer == answer)
+            .filter_map(|e| e.verdict.as_ref())
+            .find(|v| !matches!(v, Verdict::RateLimited { .. }))
+    }
+}
+
+/// Exclusive bounds on an answer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bounds {
+    /// The highest guess which was too low
+    pub low: Option<i128>,
+    /// The lowest guess which was too high
+    pub high: Option<i128>,
+}
+impl Bounds {
+    pub fn is_unbounded(&self) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+    pub fn contains(&self, n: i128) -> bool {
+        self.low.is_none_or(|l| n > l) && self.high.is_none_or(|h| n < h)
+    }
+}
+impl fmt::Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.low, self.high) {
+            (Some(low), Some(high)) => write!(f, "greater than {} and less than {}", low, high),
+            (Some(low), None) => write!(f, "greater than {}", low),
+            (None, Some(high)) => write!(f, "less than {}", high),
+            (None, None) => write!(f, "unbounded"),
+        }
+    }
+}
+
+/// What the history says about an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnswerStatus {
+    /// The server accepted this answer
+    Accepted,
+    /// The server accepted a different answer
+    Mismatch(String),
+    /// The server has already rejected this answer
+    Rejected(Verdict),
+    /// The answer is not between the guesses which were too low and too high
+    OutOfBounds(Bounds),
+    /// Nothing is known about the answer
+    Unknown,
+}
+impl AnswerStatus {
+    /// Whether the answer is known to be (or is very likely to be) wrong
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Self::Mismatch(_) | Self::Rejected(_) | Self::OutOfBounds(_)
+        )
+    }
+}
+impl fmt::Display for AnswerStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Accepted => write!(f, "is the accepted answer"),
+            Self::Mismatch(correct) => {
+                write!(f, "does not match the accepted answer ({})", correct)
+            }
+            Self::Rejected(Verdict::TooHigh) => write!(f, "was already rejected as too high"),
+            Self::Rejected(Verdict::TooLow) => write!(f, "was already rejected as too low"),
+            Self::Rejected(_) => write!(f, "was already rejected"),
+            Self::OutOfBounds(bounds) => {
+                write!(f, "is outside the known bounds: the answer is {}", bounds)
+            }
+            Self::Unknown => write!(f, "has not been checked"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum HistoryError {
+    Io(PathBuf, io::Error),
+    /// The file, line number and error
+    Parse(PathBuf, usize, serde_json::Error),
+}
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => write!(f, "Failed to access \"{}\": {}", path.display(), e),
+            Self::Parse(path, line, e) => write!(
+                f,
+                "Invalid answer history in \"{}\" on line {}: {}",
+                path.display(),
+                line,
+                e
+            ),
+        }
+    }
+}
+impl std::error::Error for HistoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(_, e) => Some(e),
+            Self::Parse(_, _, e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(guesses: &[(u8, &str, Option<Verdict>)]) -> History {
+        History {
+            path: PathBuf::new(),
+            entries: guesses
+                .iter()
+                .map(|(part, answer, verdict)| Entry::new(*part, answer, verdict.clone()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn bounds_from_guesses() {
+        let history = history(&[
+            (1, "100", Some(Verdict::TooLow)),
+            (1, "500", Some(Verdict::TooHigh)),
+            (1, "150", Some(Verdict::TooLow)),
+            (1, "400", Some(Verdict::TooHigh)),
+            (1, "300", Some(Verdict::Incorrect)),
+            (1, "50", None),
+            (2, "1000", Some(Verdict::TooHigh)),
+        ]);
+        let bounds = Bounds {
+            low: Some(150),
+            high: Some(400),
+        };
+        assert_eq!(history.bounds(1), bounds);
+        assert_eq!(history.bounds(2).to_string(), "less than 1000");
+        assert_eq!(history.status(1, "200"), AnswerStatus::Unknown);
+        assert_eq!(
+            history.status(1, "150"),
+            AnswerStatus::Rejected(Verdict::TooLow)
+        );
+        assert_eq!(
+            history.status(1, "300"),
+            AnswerStatus::Rejected(Verdict::Incorrect)
+        );
+        assert_eq!(history.status(1, "120"), AnswerStatus::OutOfBounds(bounds));
+        assert_eq!(history.status(1, "450"), AnswerStatus::OutOfBounds(bounds));
+        assert_eq!(
+            history.status(2, "1001").to_string(),
+            "is outside the known bounds: the answer is less than 1000"
+        );
+        assert_eq!(history.status(2, "abc"), AnswerStatus::Unknown);
+    }
+
+    #[test]
+    fn accepted_answer() {
+        let history = history(&[
+            (1, "100", Some(Verdict::TooLow)),
+            (1, "42", Some(Verdict::RateLimited { wait_secs: None })),
+            (1, "200", Some(Verdict::Correct)),
+        ]);
+        assert_eq!(history.correct(1), Some("200"));
+        assert_eq!(history.status(1, "200"), AnswerStatus::Accepted);
+        assert_eq!(
+            history.status(1, "42"),
+            AnswerStatus::Mismatch("200".to_string())
+        );
+        assert_eq!(history.verdict(1, "42"), None);
+        assert_eq!(history.correct(2), None);
+    }
+}
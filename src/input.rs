@@ -0,0 +1,139 @@
+use std::{
+    env, fmt, fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+/// The environment variable which can be used to override the default input directory
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where to read the puzzle input from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// A directory laid out as `<dir>/<yyyy>/<dd>.txt`
+    Dir(PathBuf),
+    /// A single file, used regardless of the year and day being run
+    File(PathBuf),
+    /// Read the whole of stdin
+    Stdin,
+}
+impl InputSource {
+    /// The default input directory: the value of `AOC_INPUT_DIR` if set, otherwise an `input`
+    /// directory on the same level as this repository
+    pub fn default_dir() -> PathBuf {
+        env::var_os(INPUT_DIR_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../input")))
+    }
+    /// Parse the value of the `--input` argument. `-` is treated as stdin
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            Self::Stdin
+        } else {
+            Self::File(arg.into())
+        }
+    }
+    /// The path the input for the given day will be read from, if it is read from a file
+    pub fn path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        match self {
+            Self::Dir(dir) => Some(dir.join(year.to_string()).join(format!("{:02}.txt", day))),
+            Self::File(path) => Some(path.clone()),
+            Self::Stdin => None,
+        }
+    }
+    pub fn load(&self, year: u16, day: u8) -> Result<String, InputError> {
+        match self.path(year, day) {
+            Some(path) => fs::read_to_string(&path).map_err(|e| InputError::Read(path, e)),
+            None => {
+                let mut buf = String::new();
+                io::stdin()
+                    .read_to_string(&mut buf)
+                    .map_err(InputError::Stdin)?;
+                Ok(buf)
+            }
+        }
+    }
+}
+impl Default for InputSource {
+    fn default() -> Self {
+        Self::Dir(Self::default_dir())
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Read(PathBuf, io::Error),
+    Stdin(io::Error),
+}
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(path, e) => {
+                write!(f, "Failed to read input file \"{}\": {}", path.display(), e)
+            }
+            Self::Stdin(e) => write!(f, "Failed to read input from stdin: {}", e),
+        }
+    }
+}
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Read(_, e) | Self::Stdin(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_paths() {
+        let dir = InputSource::Dir("inputs".into());
+        assert_eq!(dir.path(2023, 6), Some(PathBuf::from("inputs/2023/06.txt")));
+        assert_eq!(
+            dir.path(2022, 25),
+            Some(PathBuf::from("inputs/2022/25.txt"))
+        );
+        let file = InputSource::from_arg("example.txt");
+        assert_eq!(file, InputSource::File("example.txt".into()));
+        assert_eq!(file.path(2023, 6), Some(PathBuf::from("example.txt")));
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(InputSource::Stdin.path(2023, 6), None);
+    }
+
+    #[test]
+    fn input_dir_override() {
+        // No other test sets the variable, so the value read back is the one set here
+        let previous = env::var_os(INPUT_DIR_VAR);
+        env::set_var(INPUT_DIR_VAR, "/tmp/aoc-input");
+        assert_eq!(InputSource::default_dir(), PathBuf::from("/tmp/aoc-input"));
+        assert_eq!(
+            InputSource::default(),
+            InputSource::Dir("/tmp/aoc-input".into())
+        );
+        env::remove_var(INPUT_DIR_VAR);
+        assert_eq!(
+            InputSource::default_dir(),
+            PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../input"))
+        );
+        if let Some(previous) = previous {
+            env::set_var(INPUT_DIR_VAR, previous);
+        }
+    }
+
+    #[test]
+    fn load_from_dir() {
+        let dir = env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("2023")).unwrap();
+        fs::write(dir.join("2023").join("06.txt"), "Time: 7").unwrap();
+        let source = InputSource::Dir(dir.clone());
+        assert_eq!(source.load(2023, 6).unwrap(), "Time: 7");
+        assert!(matches!(
+            source.load(2023, 7),
+            Err(InputError::Read(path, _)) if path == dir.join("2023").join("07.txt")
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
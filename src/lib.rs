@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod answers;
+pub mod bench;
+pub mod client;
+pub mod config;
+mod dispatch;
+#[allow(unused_imports)]
+use dispatch::*;
+mod dispatcher;
+#[allow(unused_imports)]
+use dispatcher::*;
+pub mod examples;
+pub mod geometry;
+pub mod grid;
+pub use examples::Example;
+pub use grid::Grid;
+pub mod history;
+pub mod input;
+pub use input::InputSource;
+pub mod params;
+pub mod parse;
+pub use params::{Param, Params};
+pub mod pool;
+pub mod ranges;
+pub mod registry;
+pub use registry::{days, examples, run, solution, solutions, SolutionInfo};
+pub mod report;
+pub use report::{Failure, PartReport, Phase, RunReport};
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub use solution::{RunPart, Solution, SolutionBuilder};
+
+// The year modules, `YEARS`, `ALL_YEARS` and `SOLUTIONS` are generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/years.rs"));
@@ -0,0 +1,147 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+/// A named value which a solution can be tuned with, such as a size which is different for the
+/// examples and the real input. Declared by a day in its `PARAMS` constant:
+/// ```ignore
+/// const ROW: Param = Param::new("row", "2000000", "The row to count the positions in");
+/// pub const PARAMS: &[Param] = &[ROW];
+///
+/// crate::aoc! {
+///     solution: |params| {
+///         let row = ROW.get::<i64>(params);
+///         ...
+///     },
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    /// Used unless the value is overridden
+    pub default: &'static str,
+    pub help: &'static str,
+}
+impl Param {
+    pub const fn new(name: &'static str, default: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            default,
+            help,
+        }
+    }
+    /// The overridden value, or the default
+    pub fn value<'a>(&self, params: &'a Params) -> &'a str {
+        params.get(self.name).unwrap_or(self.default)
+    }
+    /// Parse the overridden value, or the default
+    pub fn get<T>(&self, params: &Params) -> Result<T, ParamError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self.value(params);
+        value.parse().map_err(|e: T::Err| ParamError {
+            name: self.name,
+            value: value.to_string(),
+            reason: e.to_string(),
+        })
+    }
+}
+
+/// Overridden parameter values, from the command line or an example.
+/// Parameters which are not set use their default
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(HashMap<String, String>);
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.0.insert(name.to_string(), value.to_string());
+    }
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+    /// The names of the parameters which have been set
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+impl<K: AsRef<str>, V: AsRef<str>> FromIterator<(K, V)> for Params {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut params = Self::new();
+        params.extend(iter);
+        params
+    }
+}
+impl<K: AsRef<str>, V: AsRef<str>> Extend<(K, V)> for Params {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (name, value) in iter {
+            self.set(name.as_ref(), value.as_ref());
+        }
+    }
+}
+
+/// Parse a `NAME=VALUE` pair, as passed to `--param`
+pub fn parse_assignment(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("Expected NAME=VALUE, got \"{}\"", s)),
+    }
+}
+
+/// A parameter value which could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamError {
+    pub name: &'static str,
+    pub value: String,
+    pub reason: String,
+}
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid value \"{}\" for parameter \"{}\": {}",
+            self.value, self.name, self.reason
+        )
+    }
+}
+impl std::error::Error for ParamError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROW: Param = Param::new("row", "2000000", "");
+
+    #[test]
+    fn defaults_and_overrides() {
+        let mut params = Params::new();
+        assert_eq!(ROW.get::<i64>(&params), Ok(2_000_000));
+        params.set("row", "10");
+        assert_eq!(ROW.get::<i64>(&params), Ok(10));
+        params.set("row", "ten");
+        assert_eq!(
+            ROW.get::<i64>(&params).unwrap_err().to_string(),
+            "Invalid value \"ten\" for parameter \"row\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn assignments() {
+        assert_eq!(
+            parse_assignment("row = 10"),
+            Ok(("row".to_string(), "10".to_string()))
+        );
+        assert_eq!(
+            parse_assignment("a=b=c"),
+            Ok(("a".to_string(), "b=c".to_string()))
+        );
+        assert!(parse_assignment("row").is_err());
+        assert!(parse_assignment("=10").is_err());
+    }
+}
@@ -0,0 +1,418 @@
+use std::{error::Error, fmt, ops::Deref, str::FromStr};
+
+/// A piece of the input, which knows where it is so that errors can point at it.
+/// It derefs to `str`; its own methods return spans, so positions are kept as it is split up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    text: &'a str,
+    /// Counted from 1
+    line: usize,
+    /// Counted in characters, from 1
+    column: usize,
+}
+impl<'a> Span<'a> {
+    /// The whole input, starting at line 1, column 1
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            text: input,
+            line: 1,
+            column: 1,
+        }
+    }
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+    pub fn line(&self) -> usize {
+        self.line
+    }
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The span of `inner`, which must be a slice of this span's text
+    fn within(self, inner: &'a str) -> Self {
+        let start = (inner.as_ptr() as usize)
+            .checked_sub(self.text.as_ptr() as usize)
+            .filter(|start| start + inner.len() <= self.text.len())
+            .expect("The text is not part of the span");
+        let prefix = &self.text[..start];
+        let (line, column) = match prefix.rfind('\n') {
+            Some(i) => (
+                self.line + prefix.matches('\n').count(),
+                prefix[i + 1..].chars().count() + 1,
+            ),
+            None => (self.line, self.column + prefix.chars().count()),
+        };
+        Self {
+            text: inner,
+            line,
+            column,
+        }
+    }
+    /// The empty span just after the end of this one
+    pub fn end(self) -> Self {
+        self.within(&self.text[self.text.len()..])
+    }
+    pub fn trim(self) -> Self {
+        self.within(self.text.trim())
+    }
+    /// The lines which are not blank, without their line endings
+    pub fn lines(self) -> impl Iterator<Item = Span<'a>> {
+        self.text
+            .split('\n')
+            .enumerate()
+            .map(move |(i, line)| Span {
+                text: line.strip_suffix('\r').unwrap_or(line),
+                line: self.line + i,
+                column: if i == 0 { self.column } else { 1 },
+            })
+            .filter(|line| !line.text.trim().is_empty())
+    }
+    /// Groups of lines separated by one or more blank lines, without the line ending of the last
+    /// line. Leading whitespace is kept, as it can be significant in a drawing
+    pub fn blocks(self) -> impl Iterator<Item = Span<'a>> {
+        let mut blocks = Vec::new();
+        let mut current: Option<(Span<'a>, Span<'a>)> = None;
+        for line in self.lines() {
+            current = match current {
+                Some((first, last)) if line.line == last.line + 1 => Some((first, line)),
+                _ => {
+                    blocks.extend(current);
+                    Some((line, line))
+                }
+            };
+        }
+        blocks.extend(current);
+        blocks.into_iter().map(move |(first, last)| {
+            let start = first.text.as_ptr() as usize - self.text.as_ptr() as usize;
+            let end = last.text.as_ptr() as usize - self.text.as_ptr() as usize + last.text.len();
+            Span {
+                text: &self.text[start..end],
+                ..first
+            }
+        })
+    }
+    /// The pieces between each `delimiter`
+    pub fn split(self, delimiter: &'a str) -> impl Iterator<Item = Span<'a>> {
+        self.text.split(delimiter).map(move |s| self.within(s))
+    }
+    /// The text before and after the first `delimiter`
+    pub fn split_once(self, delimiter: &str) -> Result<(Span<'a>, Span<'a>), ParseError> {
+        match self.text.split_once(delimiter) {
+            Some((before, after)) => Ok((self.within(before), self.within(after))),
+            None => Err(self
+                .end()
+                .error(ParseErrorKind::Expected(delimiter.to_string()))),
+        }
+    }
+    /// A `key: value` line, split at the first colon. Both sides are trimmed
+    pub fn key_value(self) -> Result<(Span<'a>, Span<'a>), ParseError> {
+        let (key, value) = self.split_once(":")?;
+        Ok((key.trim(), value.trim()))
+    }
+
+    /// Parse the whole span with `FromStr`
+    pub fn parse<T>(self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.text.parse().map_err(|e| self.invalid(e))
+    }
+    /// Numbers separated by whitespace and/or commas, such as `1 2  3` or `1, 2, 3`
+    pub fn numbers<T>(self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|s| !s.is_empty())
+            .map(|s| self.within(s).parse())
+            .collect()
+    }
+    /// Match the span against a template of literal text and `{name}` placeholders, such as
+    /// `"move {count} from {from} to {to}"`, parsing the text matched by each placeholder into
+    /// the matching element of the tuple. A placeholder matches everything up to the first
+    /// occurrence of the text after it, or the rest of the span if it is at the end.
+    /// Panics if the template is invalid, or has a different number of placeholders to the tuple
+    pub fn template<T: FromFields<'a>>(self, template: &str) -> Result<T, ParseError> {
+        let pieces = template_pieces(template).collect::<Vec<_>>();
+        let placeholders = pieces.iter().filter(|p| matches!(p, Piece::Field)).count();
+        assert_eq!(
+            placeholders,
+            T::LEN,
+            "The template {:?} has the wrong number of placeholders",
+            template
+        );
+        let mut fields = Vec::new();
+        let mut rest = self.text;
+        for (i, piece) in pieces.iter().enumerate() {
+            match piece {
+                Piece::Literal(literal) => {
+                    rest = rest.strip_prefix(literal).ok_or_else(|| {
+                        self.within(rest)
+                            .error(ParseErrorKind::Expected(literal.to_string()))
+                    })?;
+                }
+                Piece::Field => {
+                    let end = match pieces.get(i + 1) {
+                        Some(Piece::Literal(next)) => rest.find(next).ok_or_else(|| {
+                            self.end().error(ParseErrorKind::Expected(next.to_string()))
+                        })?,
+                        Some(Piece::Field) => panic!(
+                            "The template {:?} has placeholders which are not separated",
+                            template
+                        ),
+                        None => rest.len(),
+                    };
+                    fields.push(self.within(&rest[..end]));
+                    rest = &rest[end..];
+                }
+            }
+        }
+        if !rest.is_empty() {
+            let rest = self.within(rest);
+            return Err(rest.error(ParseErrorKind::Trailing(rest.text.to_string())));
+        }
+        T::from_fields(&fields)
+    }
+
+    pub fn error(self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+    /// An error for a value which is not valid for the given reason
+    pub fn invalid(self, reason: impl fmt::Display) -> ParseError {
+        self.error(ParseErrorKind::Invalid {
+            value: self.text.to_string(),
+            reason: reason.to_string(),
+        })
+    }
+}
+impl Deref for Span<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.text
+    }
+}
+impl fmt::Display for Span<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.text.fmt(f)
+    }
+}
+
+/// The lines of the input which are not blank. See [`Span::lines`]
+pub fn lines(input: &str) -> impl Iterator<Item = Span<'_>> {
+    Span::new(input).lines()
+}
+/// The groups of lines in the input separated by blank lines. See [`Span::blocks`]
+pub fn blocks(input: &str) -> impl Iterator<Item = Span<'_>> {
+    Span::new(input).blocks()
+}
+
+enum Piece<'t> {
+    Literal(&'t str),
+    /// A placeholder. Its name is only there for the reader of the template
+    Field,
+}
+fn template_pieces(template: &str) -> impl Iterator<Item = Piece<'_>> {
+    let mut rest = template;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let piece = match rest.strip_prefix('{') {
+            Some(field) => {
+                let (_, after) = field
+                    .split_once('}')
+                    .unwrap_or_else(|| panic!("Unclosed placeholder in {:?}", template));
+                rest = after;
+                Piece::Field
+            }
+            None => {
+                let end = rest.find('{').unwrap_or(rest.len());
+                let (literal, after) = rest.split_at(end);
+                rest = after;
+                Piece::Literal(literal)
+            }
+        };
+        Some(piece)
+    })
+}
+
+/// A value which can be parsed from the text matched by a template placeholder
+pub trait FromSpan<'a>: Sized {
+    fn from_span(span: Span<'a>) -> Result<Self, ParseError>;
+}
+impl<'a> FromSpan<'a> for Span<'a> {
+    fn from_span(span: Span<'a>) -> Result<Self, ParseError> {
+        Ok(span)
+    }
+}
+impl<'a> FromSpan<'a> for &'a str {
+    fn from_span(span: Span<'a>) -> Result<Self, ParseError> {
+        Ok(span.text)
+    }
+}
+macro_rules! from_span_via_from_str {
+    ($($t:ty),+) => {$(
+        impl FromSpan<'_> for $t {
+            fn from_span(span: Span<'_>) -> Result<Self, ParseError> {
+                span.parse()
+            }
+        }
+    )+};
+}
+from_span_via_from_str!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, String
+);
+
+/// A tuple which can be parsed from the fields matched by a template
+pub trait FromFields<'a>: Sized {
+    /// The number of fields
+    const LEN: usize;
+    fn from_fields(fields: &[Span<'a>]) -> Result<Self, ParseError>;
+}
+macro_rules! from_fields_for_tuple {
+    ($($t:ident),+) => {
+        impl<'a, $($t: FromSpan<'a>),+> FromFields<'a> for ($($t,)+) {
+            const LEN: usize = [$(stringify!($t)),+].len();
+
+            fn from_fields(fields: &[Span<'a>]) -> Result<Self, ParseError> {
+                let mut fields = fields.iter();
+                Ok(($($t::from_span(*fields.next().unwrap())?,)+))
+            }
+        }
+    };
+}
+from_fields_for_tuple!(A);
+from_fields_for_tuple!(A, B);
+from_fields_for_tuple!(A, B, C);
+from_fields_for_tuple!(A, B, C, D);
+from_fields_for_tuple!(A, B, C, D, E);
+from_fields_for_tuple!(A, B, C, D, E, F);
+
+/// An error in the input, and where it is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// Some literal text which was missing
+    Expected(String),
+    Invalid {
+        value: String,
+        reason: String,
+    },
+    /// Text left over after the end of a template
+    Trailing(String),
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ParseErrorKind::Expected(s) => write!(f, "expected {:?}", s),
+            ParseErrorKind::Invalid { value, reason } => {
+                write!(f, "invalid value {:?}: {}", value, reason)
+            }
+            ParseErrorKind::Trailing(s) => write!(f, "unexpected {:?} at the end", s),
+        }
+    }
+}
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_and_blocks() {
+        let input = "a: 1\n\n  b\r\nc\n\n\n d\n";
+        assert_eq!(
+            lines(input)
+                .map(|l| (l.line(), l.as_str()))
+                .collect::<Vec<_>>(),
+            [(1, "a: 1"), (3, "  b"), (4, "c"), (7, " d")]
+        );
+        let blocks = blocks(input).collect::<Vec<_>>();
+        assert_eq!(
+            blocks.iter().map(|b| b.as_str()).collect::<Vec<_>>(),
+            ["a: 1", "  b\r\nc", " d"]
+        );
+        assert_eq!(blocks[1].lines().nth(1).map(|l| l.line()), Some(4));
+
+        let (key, value) = blocks[0].key_value().unwrap();
+        assert_eq!((key.as_str(), value.parse::<u8>()), ("a", Ok(1)));
+        assert_eq!(
+            blocks[2].key_value(),
+            Err(ParseError {
+                line: 7,
+                column: 3,
+                kind: ParseErrorKind::Expected(":".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn numbers() {
+        let line = Span::new("x\n  1, 2 -3,,4  5");
+        let line = line.lines().nth(1).unwrap();
+        assert_eq!(line.numbers::<i8>(), Ok(vec![1, 2, -3, 4, 5]));
+        let error = line.numbers::<u8>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 8));
+        assert_eq!(
+            error.to_string(),
+            "Line 2, column 8: invalid value \"-3\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn templates() {
+        let line = Span::new("move 12 from 3 to 4");
+        assert_eq!(
+            line.template::<(usize, u8, u8)>("move {count} from {from} to {to}"),
+            Ok((12, 3, 4))
+        );
+        let (verb, rest) = line.template::<(&str, Span)>("{verb} {rest}").unwrap();
+        assert_eq!(
+            (verb, rest.as_str(), rest.column()),
+            ("move", "12 from 3 to 4", 6)
+        );
+
+        let error = |template| {
+            let e = line.template::<(i8, u8, u8)>(template).unwrap_err();
+            (e.column, e.kind)
+        };
+        let expected = |column, s: &str| (column, ParseErrorKind::Expected(s.to_string()));
+        assert_eq!(error("shift {n} from {n} to {n}"), expected(1, "shift "));
+        assert_eq!(error("move {n} from {n} by {n}"), expected(20, " by "));
+        assert_eq!(error("move {n} from {n} to {n}!"), expected(20, "!"));
+        assert_eq!(
+            Span::new("move 300 from 1 to 2").template::<(u8, u8, u8)>("move {n} from {n} to {n}"),
+            Err(ParseError {
+                line: 1,
+                column: 6,
+                kind: ParseErrorKind::Invalid {
+                    value: "300".to_string(),
+                    reason: "number too large to fit in target type".to_string()
+                }
+            })
+        );
+        assert_eq!(
+            line.template::<(u8,)>("move {n} from 3"),
+            Err(ParseError {
+                line: 1,
+                column: 15,
+                kind: ParseErrorKind::Trailing(" to 4".to_string())
+            })
+        );
+    }
+}
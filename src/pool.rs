@@ -0,0 +1,99 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Call `work` on each item, using up to `jobs` worker threads.
+/// Each result is passed to `emit` on the calling thread in the order of `items`, as soon as it and
+/// every earlier result are ready, so output stays in order however the work is scheduled.
+/// With a single job (or item) everything is run on the calling thread
+pub fn run_ordered<T, R, W, E>(items: &[T], jobs: usize, work: W, mut emit: E)
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> R + Sync,
+    E: FnMut(&T, R),
+{
+    if jobs <= 1 || items.len() <= 1 {
+        for item in items {
+            emit(item, work(item));
+        }
+        return;
+    }
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let tx = tx.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                if tx.send((index, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        // Only the workers hold senders now, so the loop below ends when they have all finished
+        drop(tx);
+        let mut pending = items.iter().map(|_| None).collect::<Vec<Option<R>>>();
+        let mut emitted = 0;
+        for (index, result) in rx {
+            pending[index] = Some(result);
+            while let Some(result) = pending.get_mut(emitted).and_then(Option::take) {
+                emit(&items[emitted], result);
+                emitted += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Barrier, time::Duration};
+
+    use super::*;
+
+    #[test]
+    fn results_are_emitted_in_order() {
+        let items = (0..8u64).collect::<Vec<_>>();
+        let mut emitted = Vec::new();
+        run_ordered(
+            &items,
+            4,
+            // Later items finish first
+            |&i| {
+                thread::sleep(Duration::from_millis(40 - i * 5));
+                i
+            },
+            |&i, result| {
+                assert_eq!(i, result);
+                emitted.push(i);
+            },
+        );
+        assert_eq!(emitted, items);
+    }
+
+    #[test]
+    fn items_run_concurrently() {
+        // Would deadlock if the items were not all running at the same time
+        let barrier = Barrier::new(3);
+        let mut threads = Vec::new();
+        run_ordered(
+            &[(); 3],
+            3,
+            |_| {
+                barrier.wait();
+                thread::current().id()
+            },
+            |_, id| threads.push(id),
+        );
+        threads.dedup();
+        assert_eq!(threads.len(), 3);
+    }
+}
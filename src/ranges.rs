@@ -0,0 +1,262 @@
+use std::{
+    cmp,
+    ops::{Add, Range, Sub},
+};
+
+/// A set of values, stored as sorted, non-overlapping half-open ranges. Overlapping or touching
+/// ranges are merged as they are inserted, so each range is separated from the next by a gap
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+impl<T: Copy + Ord> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Add every value in the range. Empty ranges are ignored
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // The ranges which overlap or touch the new one
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if first < last {
+            cmp::min(range.start, self.ranges[first].start)
+                ..cmp::max(range.end, self.ranges[last - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+    /// The ranges, in order
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+    /// The smallest value in the set
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    /// Every value in either set
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for r in &other.ranges {
+            union.insert(r.clone());
+        }
+        union
+    }
+    /// Every value in both sets
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (a, b) = (&self.ranges, &other.ranges);
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = Vec::new();
+        while i < a.len() && j < b.len() {
+            let start = cmp::max(a[i].start, b[j].start);
+            let end = cmp::min(a[i].end, b[j].end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            // Whichever range ends first can't overlap anything else in the other set
+            if a[i].end < b[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+    /// Every value in this set which is not in `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        let removed = &other.ranges;
+        let mut j = 0;
+        let mut ranges = Vec::new();
+        for r in &self.ranges {
+            let mut start = r.start;
+            while j < removed.len() && removed[j].end <= start {
+                j += 1;
+            }
+            // The range at `j` may also overlap the next range, so it is not skipped here
+            for cut in removed[j..].iter().take_while(|cut| cut.start < r.end) {
+                if start < cut.start {
+                    ranges.push(start..cut.start);
+                }
+                start = cmp::max(start, cut.end);
+            }
+            if start < r.end {
+                ranges.push(start..r.end);
+            }
+        }
+        Self { ranges }
+    }
+}
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Default> RangeSet<T> {
+    /// The number of values in the set
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::default(), |len, r| len + (r.end - r.start))
+    }
+}
+impl<T: Copy + Ord> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        std::iter::once(range).collect()
+    }
+}
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+impl<T: Copy + Ord> Extend<Range<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+/// A piecewise mapping which moves each of a set of non-overlapping source ranges to start at
+/// a new value. Values outside every source range are mapped to themselves
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeMap<T> {
+    /// Each source range with the value its start is mapped to, sorted by the source start
+    entries: Vec<(Range<T>, T)>,
+}
+impl<T> Default for RangeMap<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> RangeMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Map `source` onto the range of the same length starting at `dest`.
+    /// Panics if `source` overlaps a source range already in the map
+    pub fn insert(&mut self, source: Range<T>, dest: T) {
+        if source.is_empty() {
+            return;
+        }
+        let i = self
+            .entries
+            .partition_point(|(r, _)| r.start < source.start);
+        let overlaps_prev = i > 0 && self.entries[i - 1].0.end > source.start;
+        let overlaps_next = self
+            .entries
+            .get(i)
+            .is_some_and(|(r, _)| r.start < source.end);
+        assert!(
+            !overlaps_prev && !overlaps_next,
+            "A source range in a RangeMap overlaps another"
+        );
+        self.entries.insert(i, (source, dest));
+    }
+    pub fn get(&self, value: T) -> T {
+        let i = self.entries.partition_point(|(r, _)| r.end <= value);
+        match self.entries.get(i) {
+            Some((r, dest)) if r.start <= value => *dest + (value - r.start),
+            _ => value,
+        }
+    }
+    /// Map every value in the set, splitting its ranges wherever they cross the edge of a
+    /// source range
+    pub fn map_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        let mut mapped = RangeSet::new();
+        for r in set.ranges() {
+            let mut start = r.start;
+            let first = self
+                .entries
+                .partition_point(|(source, _)| source.end <= start);
+            for (source, dest) in self.entries[first..]
+                .iter()
+                .take_while(|(source, _)| source.start < r.end)
+            {
+                if start < source.start {
+                    mapped.insert(start..source.start);
+                    start = source.start;
+                }
+                let end = cmp::min(source.end, r.end);
+                mapped.insert(*dest + (start - source.start)..*dest + (end - source.start));
+                start = end;
+            }
+            if start < r.end {
+                mapped.insert(start..r.end);
+            }
+        }
+        mapped
+    }
+}
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> FromIterator<(Range<T>, T)>
+    for RangeMap<T>
+{
+    fn from_iter<I: IntoIterator<Item = (Range<T>, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (source, dest) in iter {
+            map.insert(source, dest);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_operations() {
+        let mut set = RangeSet::from_iter([5..8, 0..2, 10..12]);
+        assert_eq!(set.ranges(), [0..2, 5..8, 10..12]);
+        set.insert(2..3);
+        set.insert(7..10);
+        set.insert(20..20);
+        assert_eq!(set.ranges(), [0..3, 5..12], "touching ranges are merged");
+        assert_eq!(set.len(), 10);
+        assert!(set.contains(11) && !set.contains(12) && !set.contains(4));
+        assert_eq!(set.min(), Some(0));
+
+        let other = RangeSet::from_iter([1..6, 8..9, 11..15]);
+        assert_eq!(set.union(&other), RangeSet::from(0..15));
+        assert_eq!(
+            set.intersection(&other).ranges(),
+            [1..3, 5..6, 8..9, 11..12]
+        );
+        assert_eq!(set.difference(&other).ranges(), [0..1, 6..8, 9..11]);
+        assert_eq!(other.difference(&set).ranges(), [3..5, 12..15]);
+        assert!(set.difference(&set).is_empty());
+        assert_eq!(RangeSet::<u8>::new().len(), 0);
+    }
+
+    #[test]
+    fn map() {
+        let map = RangeMap::from_iter([(98..100u64, 50), (50..98, 52)]);
+        assert_eq!(map.get(79), 81);
+        assert_eq!(map.get(99), 51);
+        assert_eq!(map.get(10), 10);
+        assert_eq!(map.get(100), 100);
+        // 40..50 and 100..105 are unmapped, 50..60 moves to 52..62, 95..98 to 97..100 and
+        // 98..100 to 50..52
+        assert_eq!(
+            map.map_set(&RangeSet::from_iter([40..60, 95..105]))
+                .ranges(),
+            [40..62, 97..105]
+        );
+        assert_eq!(map.map_set(&(0..200).into()).len(), 200);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{Example, Param, Params, RunPart, RunReport, SOLUTIONS};
+
+/// A compiled solution for a single day, as listed in [`SOLUTIONS`]
+#[derive(Debug, Clone, Copy)]
+pub struct SolutionInfo {
+    pub year: u16,
+    pub day: u8,
+    /// The title of the puzzle, from the day's `TITLE` constant. Empty if it has not been set
+    pub title: &'static str,
+    /// 1 if only part 1 has been written, 2 if both parts have
+    pub parts_implemented: u8,
+    /// The day's `main` function
+    pub runner: fn(&str, RunPart, &Params) -> RunReport,
+    pub examples: &'static [Example],
+    /// The day's `PARAMS`, if it declares any
+    pub params: &'static [Param],
+}
+impl SolutionInfo {
+    /// Run with the default parameter values
+    pub fn run(&self, input: &str, parts: RunPart) -> RunReport {
+        self.run_with(input, parts, &Params::new())
+    }
+    pub fn run_with(&self, input: &str, parts: RunPart, params: &Params) -> RunReport {
+        (self.runner)(input, parts, params)
+    }
+    pub fn param(&self, name: &str) -> Option<&'static Param> {
+        self.params.iter().find(|p| p.name == name)
+    }
+}
+
+/// The solution for the given day, if there is one and its year has been compiled in
+pub fn solution(year: u16, day: u8) -> Option<&'static SolutionInfo> {
+    SOLUTIONS
+        .iter()
+        .find(|info| info.year == year && info.day == day)
+}
+
+/// The solutions for the given year, in order
+pub fn solutions(year: u16) -> impl Iterator<Item = &'static SolutionInfo> {
+    SOLUTIONS.iter().filter(move |info| info.year == year)
+}
+
+/// The days which have a solution for the given year, in order
+pub fn days(year: u16) -> impl Iterator<Item = u8> {
+    solutions(year).map(|info| info.day)
+}
+
+/// Returns `None` if there is no solution for the given day
+pub fn run(year: u16, day: u8, input: &str, parts: RunPart) -> Option<RunReport> {
+    solution(year, day).map(|info| info.run(input, parts))
+}
+
+pub fn examples(year: u16, day: u8) -> &'static [Example] {
+    solution(year, day).map_or(&[], |info| info.examples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_params_are_declared() {
+        for info in SOLUTIONS {
+            for example in info.examples {
+                for (name, _) in example.params {
+                    assert!(
+                        info.param(name).is_some(),
+                        "{} day {} example \"{}\" sets undeclared parameter \"{}\"",
+                        info.year,
+                        info.day,
+                        example.name,
+                        name
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,399 @@
+use std::{error::Error, fmt, time::Duration};
+
+use serde::Serialize;
+
+use crate::RunPart;
+
+/// The answer to a single part, formatted with `Display`, and how long it took to compute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartReport {
+    pub answer: String,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Setup,
+    Part1,
+    Part2,
+}
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Setup => write!(f, "setup"),
+            Self::Part1 => write!(f, "part 1"),
+            Self::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
+/// An error returned by one of the steps of a solution
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Failure {
+    pub phase: Phase,
+    /// The message of the error, followed by the message of each of its sources
+    pub chain: Vec<String>,
+}
+impl Failure {
+    pub fn new(phase: Phase, error: &dyn Error) -> Self {
+        let mut chain = vec![error.to_string()];
+        let mut source = error.source();
+        while let Some(e) = source {
+            chain.push(e.to_string());
+            source = e.source();
+        }
+        Self { phase, chain }
+    }
+}
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Error in {}: {}", self.phase, self.chain[0])?;
+        for cause in &self.chain[1..] {
+            write!(f, "\n  Caused by: {}", cause)?;
+        }
+        Ok(())
+    }
+}
+
+/// The results of running a solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunReport {
+    /// The parts which were requested
+    pub parts: RunPart,
+    /// How long the setup took. `None` if the solution has no separate setup step
+    pub setup: Option<Duration>,
+    /// `None` if part 1 was not run
+    pub part1: Option<PartReport>,
+    /// `None` if part 2 was not run
+    pub part2: Option<PartReport>,
+    /// The first error returned by a step, if any. Nothing is run after a failed setup, but
+    /// part 2 is still run after a failed part 1
+    pub failure: Option<Failure>,
+}
+impl RunReport {
+    pub fn new(parts: RunPart) -> Self {
+        Self {
+            parts,
+            setup: None,
+            part1: None,
+            part2: None,
+            failure: None,
+        }
+    }
+    pub fn part(&self, part: u8) -> Option<&PartReport> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.part(part).map(|p| p.answer.as_str())
+    }
+    /// The total time taken for the setup and every part which was run
+    pub fn total_duration(&self) -> Duration {
+        self.setup.unwrap_or_default()
+            + [&self.part1, &self.part2]
+                .into_iter()
+                .flatten()
+                .map(|p| p.duration)
+                .sum::<Duration>()
+    }
+}
+
+/// Outputs the results of runs
+pub trait Reporter {
+    /// Called before each day is run
+    fn start(&mut self, _year: u16, _day: u8, _parts: RunPart) {}
+    fn report(&mut self, year: u16, day: u8, report: &RunReport);
+    /// Called once all the requested days have been run
+    fn finish(&mut self) {}
+}
+
+/// Prints human readable results to stdout.
+/// If more than one day was run, a summary table is printed at the end
+#[derive(Debug, Default)]
+pub struct TextReporter {
+    results: Vec<(u16, u8, RunReport)>,
+}
+impl Reporter for TextReporter {
+    fn start(&mut self, year: u16, day: u8, parts: RunPart) {
+        println!(
+            "Running: year = {:?}, day = {:?}, parts = {:?}",
+            year, day, parts
+        );
+    }
+    fn report(&mut self, year: u16, day: u8, report: &RunReport) {
+        if let Some(d) = report.setup {
+            println!("Setup took {:?}", d);
+        }
+        for (n, part) in [(1, &report.part1), (2, &report.part2)] {
+            if let Some(PartReport { answer, duration }) = part {
+                match report.setup {
+                    Some(d) => println!(
+                        "Part {}: {}. Completed in {:?} ({:?} including setup)",
+                        n,
+                        answer,
+                        duration,
+                        d + *duration
+                    ),
+                    None => println!("Part {}: {}. Completed in {:?}", n, answer, duration),
+                }
+            }
+        }
+        if report.part1.is_some() && report.part2.is_some() {
+            println!("Total duration: {:?}", report.total_duration());
+        }
+        if let Some(failure) = &report.failure {
+            eprintln!("{} day {} failed. {}", year, day, failure);
+        }
+        self.results.push((year, day, report.clone()));
+    }
+    fn finish(&mut self) {
+        if self.results.len() > 1 {
+            println!();
+            print_summary(&self.results);
+        }
+    }
+}
+
+/// Prints one JSON object per line for each day run, with durations in nanoseconds:
+/// ```json
+/// {"year":2023,"day":6,"setup_nanos":null,"part1":{"answer":"288","nanos":5440},"part2":null,"total_nanos":5440,"error":null}
+/// ```
+/// If the run failed, `error` is `{"phase":"setup"|"part1"|"part2","chain":["message", "source message", ...]}`
+#[derive(Debug, Default)]
+pub struct JsonReporter;
+impl Reporter for JsonReporter {
+    fn report(&mut self, year: u16, day: u8, report: &RunReport) {
+        println!("{}", json_line(year, day, report));
+    }
+}
+
+#[derive(Serialize)]
+struct JsonPart<'r> {
+    answer: &'r str,
+    nanos: u64,
+}
+#[derive(Serialize)]
+struct JsonReport<'r> {
+    year: u16,
+    day: u8,
+    setup_nanos: Option<u64>,
+    part1: Option<JsonPart<'r>>,
+    part2: Option<JsonPart<'r>>,
+    total_nanos: u64,
+    error: &'r Option<Failure>,
+}
+
+/// The line printed by [`JsonReporter`] for a run
+fn json_line(year: u16, day: u8, report: &RunReport) -> String {
+    fn part(p: &Option<PartReport>) -> Option<JsonPart<'_>> {
+        p.as_ref().map(|p| JsonPart {
+            answer: &p.answer,
+            nanos: nanos(p.duration),
+        })
+    }
+    serde_json::to_string(&JsonReport {
+        year,
+        day,
+        setup_nanos: report.setup.map(nanos),
+        part1: part(&report.part1),
+        part2: part(&report.part2),
+        total_nanos: nanos(report.total_duration()),
+        error: &report.failure,
+    })
+    .unwrap()
+}
+
+fn nanos(d: Duration) -> u64 {
+    d.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+fn print_summary(results: &[(u16, u8, RunReport)]) {
+    let header = [
+        "Year", "Day", "Setup", "Part 1", "Time", "Part 2", "Time", "Total",
+    ];
+    print_table(header, &summary_rows(results));
+    println!(
+        "Ran {} days in {:?}",
+        results.len(),
+        results
+            .iter()
+            .map(|(_, _, report)| report.total_duration())
+            .sum::<Duration>()
+    );
+}
+
+/// A row of the summary table for each day run
+fn summary_rows(results: &[(u16, u8, RunReport)]) -> Vec<[String; 8]> {
+    fn cells(part: &Option<PartReport>, failed: bool) -> [String; 2] {
+        match part {
+            None if failed => ["FAILED".to_string(), "-".to_string()],
+            // Multi-line answers (e.g. rendered text) would break the table layout
+            Some(p) if p.answer.trim().contains('\n') => [
+                format!("({} lines)", p.answer.trim().lines().count()),
+                format!("{:?}", p.duration),
+            ],
+            Some(p) => [p.answer.clone(), format!("{:?}", p.duration)],
+            None => ["-".to_string(), "-".to_string()],
+        }
+    }
+    results
+        .iter()
+        .map(|(year, day, report)| {
+            let failed = |phase| report.failure.as_ref().is_some_and(|f| f.phase == phase);
+            let [a1, t1] = cells(&report.part1, failed(Phase::Part1));
+            let [a2, t2] = cells(&report.part2, failed(Phase::Part2));
+            [
+                year.to_string(),
+                day.to_string(),
+                match report.setup {
+                    _ if failed(Phase::Setup) => "FAILED".to_string(),
+                    Some(d) => format!("{:?}", d),
+                    None => "-".to_string(),
+                },
+                a1,
+                t1,
+                a2,
+                t2,
+                format!("{:?}", report.total_duration()),
+            ]
+        })
+        .collect()
+}
+
+/// Print a table with left-aligned columns, each as wide as its widest cell
+pub fn print_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) {
+    let mut widths = header.map(|h| h.len());
+    for row in rows.iter() {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    let line = |row: &mut dyn Iterator<Item = &str>| {
+        row.zip(widths)
+            .map(|(cell, w)| format!("{:<w$}", cell, w = w))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+    println!("{}", line(&mut header.into_iter()));
+    println!("{}", widths.map(|w| "-".repeat(w)).join("-+-"));
+    for row in rows.iter() {
+        println!("{}", line(&mut row.iter().map(String::as_str)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(answer: &str, micros: u64) -> Option<PartReport> {
+        Some(PartReport {
+            answer: answer.to_string(),
+            duration: Duration::from_micros(micros),
+        })
+    }
+
+    /// Setup took 100µs, part 1 answered 288 in 20µs, and part 2 failed
+    fn failed_report() -> RunReport {
+        RunReport {
+            setup: Some(Duration::from_micros(100)),
+            part1: part("288", 20),
+            failure: Some(Failure {
+                phase: Phase::Part2,
+                chain: vec!["Overflow".to_string(), "Line 3".to_string()],
+            }),
+            ..RunReport::new(RunPart::All)
+        }
+    }
+
+    #[test]
+    fn run_report() {
+        let report = failed_report();
+        assert_eq!(report.answer(1), Some("288"));
+        assert_eq!(report.answer(2), None);
+        assert_eq!(report.part(3), None);
+        assert_eq!(report.total_duration(), Duration::from_micros(120));
+
+        let report = RunReport {
+            part1: part("1", 5),
+            part2: part("2", 7),
+            ..RunReport::new(RunPart::All)
+        };
+        assert_eq!(report.total_duration(), Duration::from_micros(12));
+        assert_eq!(
+            RunReport::new(RunPart::Part1).total_duration(),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn failure_chain() {
+        assert_eq!(
+            failed_report().failure.unwrap().to_string(),
+            "Error in part 2: Overflow\n  Caused by: Line 3"
+        );
+    }
+
+    #[test]
+    fn summary() {
+        let drawing = RunReport {
+            part1: part("\n#..#\n####\n", 3),
+            ..RunReport::new(RunPart::Part1)
+        };
+        let failed_setup = RunReport {
+            setup: Some(Duration::from_micros(50)),
+            failure: Some(Failure {
+                phase: Phase::Setup,
+                chain: vec!["Empty".to_string()],
+            }),
+            ..RunReport::new(RunPart::All)
+        };
+        let rows = summary_rows(&[
+            (2023, 6, failed_report()),
+            (2022, 10, drawing),
+            (2023, 10, failed_setup),
+        ]);
+        assert_eq!(
+            rows[0],
+            ["2023", "6", "100µs", "288", "20µs", "FAILED", "-", "120µs"].map(String::from)
+        );
+        assert_eq!(
+            rows[1],
+            ["2022", "10", "-", "(2 lines)", "3µs", "-", "-", "3µs"].map(String::from)
+        );
+        assert_eq!(
+            rows[2],
+            ["2023", "10", "FAILED", "-", "-", "-", "-", "50µs"].map(String::from)
+        );
+    }
+
+    #[test]
+    fn json() {
+        let json = json_line(2023, 6, &failed_report());
+        assert!(!json.contains('\n'), "one line per run");
+        let json = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "year": 2023,
+                "day": 6,
+                "setup_nanos": 100_000,
+                "part1": { "answer": "288", "nanos": 20_000 },
+                "part2": null,
+                "total_nanos": 120_000,
+                "error": { "phase": "part2", "chain": ["Overflow", "Line 3"] },
+            })
+        );
+
+        let json = json_line(2023, 1, &RunReport::new(RunPart::All));
+        assert_eq!(
+            json,
+            r#"{"year":2023,"day":1,"setup_nanos":null,"part1":null,"part2":null,"total_nanos":0,"error":null}"#
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::InputSource;
+
+const TEMPLATE: &str = r#"pub const TITLE: &str = "";
+
+crate::examples! {
+    "example" => r"" => (_, _),
+}
+
+crate::aoc! {
+    setup: |input| input.split("\n").filter(|s| !s.is_empty()).collect::<Vec<_>>(),
+    part1: |_lines| 0,
+}
+"#;
+
+/// The contents of `src/y<yyyy>/mod.rs`, which pulls in the code generated by build.rs
+fn year_module(year: u16) -> String {
+    format!(
+        r#"// The `pub mod dNN` declarations (and the tests of any answers recorded in `answers/`) are
+// generated by build.rs from the `dNN.rs` files in this directory
+include!(concat!(env!("OUT_DIR"), "/y{}.rs"));
+"#,
+        year
+    )
+}
+
+/// What was created by [`new_day`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scaffold {
+    pub day_file: PathBuf,
+    /// `Some` if the year directory did not already exist. Its `mod.rs` is created along with it
+    pub year_dir: Option<PathBuf>,
+    /// `Some` if the input file did not already exist
+    pub input_file: Option<PathBuf>,
+}
+
+/// Create `src/y<yyyy>/d<dd>.rs` from the template, along with the year directory (and its
+/// `mod.rs`) and an empty input file if they do not exist. An existing day file is never overwritten
+pub fn new_day(year: u16, day: u8, input: &InputSource) -> Result<Scaffold, ScaffoldError> {
+    new_day_in(
+        Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src")),
+        year,
+        day,
+        input,
+    )
+}
+
+/// [`new_day`], with the year directories in `src` instead of this crate's `src`
+fn new_day_in(
+    src: &Path,
+    year: u16,
+    day: u8,
+    input: &InputSource,
+) -> Result<Scaffold, ScaffoldError> {
+    let year_dir = src.join(format!("y{}", year));
+    let day_file = year_dir.join(format!("d{:02}.rs", day));
+    if day_file.exists() {
+        return Err(ScaffoldError::Exists(day_file));
+    }
+    let created_dir = !year_dir.exists();
+    if created_dir {
+        fs::create_dir_all(&year_dir).map_err(|e| ScaffoldError::Io(year_dir.clone(), e))?;
+        let module = year_dir.join("mod.rs");
+        fs::write(&module, year_module(year)).map_err(|e| ScaffoldError::Io(module, e))?;
+    }
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&day_file)
+        .and_then(|mut f| f.write_all(TEMPLATE.as_bytes()))
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::AlreadyExists => ScaffoldError::Exists(day_file.clone()),
+            _ => ScaffoldError::Io(day_file.clone(), e),
+        })?;
+    let input_file = match input.path(year, day) {
+        Some(path) if !path.exists() => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| ScaffoldError::Io(parent.into(), e))?;
+            }
+            fs::File::create(&path).map_err(|e| ScaffoldError::Io(path.clone(), e))?;
+            Some(path)
+        }
+        _ => None,
+    };
+    Ok(Scaffold {
+        day_file,
+        year_dir: created_dir.then_some(year_dir),
+        input_file,
+    })
+}
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    Exists(PathBuf),
+    Io(PathBuf, io::Error),
+}
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exists(path) => write!(f, "\"{}\" already exists", path.display()),
+            Self::Io(path, e) => write!(f, "Failed to create \"{}\": {}", path.display(), e),
+        }
+    }
+}
+impl std::error::Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Exists(_) => None,
+            Self::Io(_, e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaffold_day() {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let src = dir.join("src");
+        let input = InputSource::Dir(dir.join("input"));
+
+        let created = new_day_in(&src, 2023, 7, &input).unwrap();
+        assert_eq!(
+            created,
+            Scaffold {
+                day_file: src.join("y2023").join("d07.rs"),
+                year_dir: Some(src.join("y2023")),
+                input_file: Some(dir.join("input").join("2023").join("07.txt")),
+            }
+        );
+        assert_eq!(fs::read_to_string(&created.day_file).unwrap(), TEMPLATE);
+        assert_eq!(
+            fs::read_to_string(src.join("y2023").join("mod.rs")).unwrap(),
+            year_module(2023)
+        );
+        assert_eq!(fs::read_to_string(created.input_file.unwrap()).unwrap(), "");
+
+        // An existing day is left alone
+        fs::write(&created.day_file, "// Solved").unwrap();
+        assert!(matches!(
+            new_day_in(&src, 2023, 7, &input),
+            Err(ScaffoldError::Exists(path)) if path == created.day_file
+        ));
+        assert_eq!(fs::read_to_string(&created.day_file).unwrap(), "// Solved");
+
+        // The year directory and the input are only created if they do not exist
+        let input_file = dir.join("input").join("2023").join("08.txt");
+        fs::write(&input_file, "1 2 3").unwrap();
+        let created = new_day_in(&src, 2023, 8, &input).unwrap();
+        assert_eq!((created.year_dir, created.input_file), (None, None));
+        assert_eq!(fs::read_to_string(&input_file).unwrap(), "1 2 3");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
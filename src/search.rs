@@ -0,0 +1,301 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// The cheapest path found by a search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    /// The total cost of the path. For a BFS this is the number of steps
+    pub cost: C,
+    /// Every state on the path, from the start to the goal inclusive
+    pub states: Vec<S>,
+}
+impl<S, C> Path<S, C> {
+    pub fn start(&self) -> &S {
+        &self.states[0]
+    }
+    pub fn goal(&self) -> &S {
+        &self.states[self.states.len() - 1]
+    }
+}
+
+/// Every state reached, with the cheapest way to reach it
+struct Visited<S, C> {
+    /// The state, the index of the state it was reached from and the cost of reaching it
+    nodes: Vec<(S, Option<usize>, C)>,
+    index: HashMap<S, usize>,
+}
+impl<S: Clone + Eq + Hash, C: Copy> Visited<S, C> {
+    fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+    fn add(&mut self, state: S, parent: Option<usize>, cost: C) -> usize {
+        let i = self.nodes.len();
+        self.index.insert(state.clone(), i);
+        self.nodes.push((state, parent, cost));
+        i
+    }
+    fn path(&self, mut i: usize) -> Path<S, C> {
+        let cost = self.nodes[i].2;
+        let mut states = vec![self.nodes[i].0.clone()];
+        while let Some(parent) = self.nodes[i].1 {
+            states.push(self.nodes[parent].0.clone());
+            i = parent;
+        }
+        states.reverse();
+        Path { cost, states }
+    }
+    fn into_costs(self) -> HashMap<S, C> {
+        self.nodes.into_iter().map(|(s, _, c)| (s, c)).collect()
+    }
+}
+
+/// Breadth first search, where every move costs 1, from any of `starts` to the first state
+/// matching `goal`. `neighbours` returns the states which can be reached from a state in one step
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let (visited, found) = bfs_visit(starts, neighbours, goal);
+    found.map(|i| visited.path(i))
+}
+
+/// The number of steps to every state reachable from any of `starts` (a flood fill)
+pub fn bfs_all<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    bfs_visit(starts, neighbours, |_| false).0.into_costs()
+}
+
+fn bfs_visit<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> (Visited<S, usize>, Option<usize>)
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !visited.index.contains_key(&start) {
+            queue.push_back(visited.add(start, None, 0));
+        }
+    }
+    while let Some(i) = queue.pop_front() {
+        let (state, _, steps) = &visited.nodes[i];
+        if goal(state) {
+            return (visited, Some(i));
+        }
+        let steps = steps + 1;
+        for next in neighbours(state) {
+            if !visited.index.contains_key(&next) {
+                queue.push_back(visited.add(next, Some(i), steps));
+            }
+        }
+    }
+    (visited, None)
+}
+
+/// Dijkstra's algorithm, from any of `starts` to the cheapest state matching `goal`.
+/// `neighbours` returns the states which can be reached from a state in one move, with the cost
+/// of the move. Costs must not be negative, and `C::default()` must be zero
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), goal)
+}
+
+/// The cost of the cheapest path to every state reachable from any of `starts`
+pub fn dijkstra_all<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+) -> HashMap<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_visit(starts, neighbours, |_| C::default(), |_| false)
+        .0
+        .into_costs()
+}
+
+/// A* search: Dijkstra's algorithm guided by `heuristic`, an estimate of the cost from a state to
+/// the nearest goal. The heuristic must never overestimate, and must not decrease by more than
+/// the cost of any move (the Manhattan distance on a grid, for example), or the path found may
+/// not be the cheapest
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let (visited, found) = astar_visit(starts, neighbours, heuristic, goal);
+    found.map(|i| visited.path(i))
+}
+
+fn astar_visit<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut goal: impl FnMut(&S) -> bool,
+) -> (Visited<S, C>, Option<usize>)
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut visited = Visited::new();
+    let mut done = Vec::new();
+    // Ordered by the estimated total cost, then the index so that entries are always comparable
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if !visited.index.contains_key(&start) {
+            let estimate = heuristic(&start);
+            queue.push(Reverse((estimate, visited.add(start, None, C::default()))));
+            done.push(false);
+        }
+    }
+    while let Some(Reverse((_, i))) = queue.pop() {
+        // A state is queued again each time a cheaper way to reach it is found, and the
+        // cheapest is always popped first
+        if done[i] {
+            continue;
+        }
+        done[i] = true;
+        let (state, _, cost) = &visited.nodes[i];
+        if goal(state) {
+            return (visited, Some(i));
+        }
+        let cost = *cost;
+        for (next, step) in neighbours(state) {
+            let next_cost = cost + step;
+            let j = match visited.index.get(&next) {
+                Some(&j) if done[j] || visited.nodes[j].2 <= next_cost => continue,
+                Some(&j) => {
+                    visited.nodes[j].1 = Some(i);
+                    visited.nodes[j].2 = next_cost;
+                    j
+                }
+                None => {
+                    done.push(false);
+                    visited.add(next, Some(i), next_cost)
+                }
+            };
+            let estimate = next_cost + heuristic(&visited.nodes[j].0);
+            queue.push(Reverse((estimate, j)));
+        }
+    }
+    (visited, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        geometry::{Dir4, Point},
+        Grid,
+    };
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#G";
+
+    fn maze() -> Grid<bool> {
+        Grid::parse(MAZE, |c| c != '#').unwrap()
+    }
+    fn open(maze: &Grid<bool>, pos: Point<usize>) -> Vec<Point<usize>> {
+        maze.neighbours4(pos).filter(|&p| maze[p]).collect()
+    }
+
+    #[test]
+    fn breadth_first() {
+        let maze = maze();
+        let start = Point::new(0, 0);
+        let goal = Point::new(7, 4);
+        let path = bfs([start], |&p| open(&maze, p), |&p| p == goal).unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.states.len(), 16);
+        assert_eq!((path.start(), path.goal()), (&start, &goal));
+        assert!(path
+            .states
+            .windows(2)
+            .all(|w| maze.neighbours4(w[0]).any(|p| p == w[1])));
+
+        // The nearest of several starts is used
+        let other = Point::new(4, 0);
+        let path = bfs([start, other], |&p| open(&maze, p), |&p| p == goal).unwrap();
+        assert_eq!((path.cost, path.start()), (7, &other));
+        let wall = Point::new(3, 0);
+        assert_eq!(bfs([start], |&p| open(&maze, p), |&p| p == wall), None);
+
+        let distances = bfs_all([start], |&p| open(&maze, p));
+        assert_eq!(distances.len(), maze.cells().filter(|(_, &c)| c).count());
+        assert_eq!(distances[&Point::new(5, 4)], 9);
+        assert_eq!(distances[&goal], 15);
+    }
+
+    #[test]
+    fn weighted() {
+        // Moving right is cheap, and moving left is expensive
+        let neighbours = |p: &Point<i32>| {
+            let p = *p;
+            Dir4::ALL.into_iter().map(move |d| {
+                let cost = match d {
+                    Dir4::Right => 1,
+                    Dir4::Left => 10,
+                    _ => 3,
+                };
+                (p.step(d), cost)
+            })
+        };
+        let start = Point::new(0, 0);
+        let goal = Point::new(-2, 1);
+        let path = dijkstra([start], neighbours, |&p| p == goal).unwrap();
+        assert_eq!(path.cost, 23);
+        assert_eq!(path.states.len(), 4);
+        let path = astar([start], neighbours, |&p| p.manhattan(goal), |&p| p == goal).unwrap();
+        assert_eq!(path.cost, 23);
+
+        let costs = dijkstra_all([Point::new(0u8, 0)], |p| {
+            [(Point::new(p.x + 1, p.y), 2), (Point::new(p.x, p.y + 1), 1)]
+                .into_iter()
+                .filter(|(p, _)| p.x < 3 && p.y < 3)
+        });
+        assert_eq!(costs.len(), 9);
+        assert_eq!(costs[&Point::new(2, 1)], 5);
+    }
+}
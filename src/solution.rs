@@ -0,0 +1,322 @@
+use std::{error::Error, fmt, fmt::Display, str::FromStr, time::Instant};
+
+use crate::{Failure, PartReport, Phase, RunReport};
+
+/// Which parts of a solution to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunPart {
+    All,
+    Part1,
+    Part2,
+}
+impl RunPart {
+    pub fn run_p1(&self) -> bool {
+        !matches!(self, Self::Part2)
+    }
+    pub fn run_p2(&self) -> bool {
+        !matches!(self, Self::Part1)
+    }
+}
+impl FromStr for RunPart {
+    type Err = String;
+
+    /// Accepts `1`, `2` or `all`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1" => Ok(Self::Part1),
+            "2" => Ok(Self::Part2),
+            "all" => Ok(Self::All),
+            _ => Err(format!("Invalid part: \"{}\". Expected 1, 2 or all", s)),
+        }
+    }
+}
+
+/// A solution for a single day, which can be run against an input
+pub trait Solution<'s> {
+    /// The number of parts which have been written
+    const PARTS: u8;
+
+    fn run(&self, input: &'s str, parts: RunPart) -> RunReport;
+}
+
+/// The number of parts written by the solution which `solution` builds, without building it.
+/// Used by [`crate::aoc!`] for the `PARTS` constant
+pub const fn parts<'s, S: Solution<'s>>(_solution: fn(&crate::Params) -> S) -> u8 {
+    S::PARTS
+}
+
+/// Placeholder answer type for parts which have not been implemented yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotImplemented {}
+impl Display for NotImplemented {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {}
+    }
+}
+
+type BoxError = Box<dyn Error>;
+
+/// Entry point for building a [`Day`].
+/// ```ignore
+/// SolutionBuilder::part1(|input| ...).part2(|input| ...)
+/// SolutionBuilder::setup(parse).part1(|parsed| ...).part2(|parsed| ...)
+/// ```
+/// Each step also has a `try_` variant, which returns a `Result`. If it fails, the error is
+/// recorded in the [`RunReport`]. A failed setup stops the run, but part 2 is still run after a
+/// failed part 1, as it does not depend on it.
+pub struct SolutionBuilder;
+impl SolutionBuilder {
+    /// Start with a setup step, the result of which is passed to each part.
+    /// Part 1 receives a reference to it, part 2 takes ownership
+    pub fn setup<'s, T, S>(setup: S) -> Day<'s, T>
+    where
+        S: Fn(&'s str) -> T + 'static,
+    {
+        Self::try_setup(move |input| Ok::<_, NoError>(setup(input)))
+    }
+    pub fn try_setup<'s, T, E, S>(setup: S) -> Day<'s, T>
+    where
+        S: Fn(&'s str) -> Result<T, E> + 'static,
+        E: Error + 'static,
+    {
+        Day {
+            setup: Box::new(move |input| setup(input).map_err(BoxError::from)),
+            has_setup: true,
+            part1: None,
+            part2: None,
+        }
+    }
+    /// Start without a setup step, so each part receives the raw input
+    pub fn part1<'s, R1, P1>(part1: P1) -> Day<'s, &'s str, R1, NotImplemented, 1>
+    where
+        P1: Fn(&'s str) -> R1 + 'static,
+        R1: Display,
+    {
+        Self::try_part1(move |input| Ok::<_, NoError>(part1(input)))
+    }
+    pub fn try_part1<'s, R1, E, P1>(part1: P1) -> Day<'s, &'s str, R1, NotImplemented, 1>
+    where
+        P1: Fn(&'s str) -> Result<R1, E> + 'static,
+        E: Error + 'static,
+        R1: Display,
+    {
+        Day {
+            setup: Box::new(Ok),
+            has_setup: false,
+            part1: Some(Box::new(move |input: &&'s str| {
+                part1(input).map_err(BoxError::from)
+            })),
+            part2: None,
+        }
+    }
+}
+
+/// Error type for infallible steps
+#[derive(Debug)]
+enum NoError {}
+impl Display for NoError {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {}
+    }
+}
+impl Error for NoError {}
+
+/// A solution made up of an (optional) setup step, and up to two parts.
+/// Unimplemented parts are skipped when run. `PARTS` is the number of parts which have been added
+pub struct Day<'s, T, R1 = NotImplemented, R2 = NotImplemented, const PARTS: u8 = 0> {
+    setup: Box<dyn Fn(&'s str) -> Result<T, BoxError> + 's>,
+    has_setup: bool,
+    part1: Option<Part1Fn<T, R1>>,
+    part2: Option<Part2Fn<T, R2>>,
+}
+type Part1Fn<T, R> = Box<dyn Fn(&T) -> Result<R, BoxError>>;
+type Part2Fn<T, R> = Box<dyn Fn(T) -> Result<R, BoxError>>;
+impl<'s, T: 's> Day<'s, T> {
+    pub fn part1<R1, P1>(self, part1: P1) -> Day<'s, T, R1, NotImplemented, 1>
+    where
+        P1: Fn(&T) -> R1 + 'static,
+        R1: Display,
+    {
+        self.try_part1(move |input| Ok::<_, NoError>(part1(input)))
+    }
+    pub fn try_part1<R1, E, P1>(self, part1: P1) -> Day<'s, T, R1, NotImplemented, 1>
+    where
+        P1: Fn(&T) -> Result<R1, E> + 'static,
+        E: Error + 'static,
+        R1: Display,
+    {
+        Day {
+            setup: self.setup,
+            has_setup: self.has_setup,
+            part1: Some(Box::new(move |input| part1(input).map_err(BoxError::from))),
+            part2: None,
+        }
+    }
+}
+impl<'s, T: 's, R1> Day<'s, T, R1, NotImplemented, 1> {
+    pub fn part2<R2, P2>(self, part2: P2) -> Day<'s, T, R1, R2, 2>
+    where
+        P2: Fn(T) -> R2 + 'static,
+        R2: Display,
+    {
+        self.try_part2(move |input| Ok::<_, NoError>(part2(input)))
+    }
+    pub fn try_part2<R2, E, P2>(self, part2: P2) -> Day<'s, T, R1, R2, 2>
+    where
+        P2: Fn(T) -> Result<R2, E> + 'static,
+        E: Error + 'static,
+        R2: Display,
+    {
+        Day {
+            setup: self.setup,
+            has_setup: self.has_setup,
+            part1: self.part1,
+            part2: Some(Box::new(move |input| part2(input).map_err(BoxError::from))),
+        }
+    }
+}
+impl<'s, T, R1, R2, const PARTS: u8> Solution<'s> for Day<'s, T, R1, R2, PARTS>
+where
+    R1: Display,
+    R2: Display,
+{
+    const PARTS: u8 = PARTS;
+
+    fn run(&self, input: &'s str, parts: RunPart) -> RunReport {
+        fn time_part<R: Display>(
+            f: impl FnOnce() -> Result<R, BoxError>,
+        ) -> Result<PartReport, BoxError> {
+            let now = Instant::now();
+            let answer = f()?;
+            let duration = now.elapsed();
+            Ok(PartReport {
+                answer: answer.to_string(),
+                duration,
+            })
+        }
+        let mut report = RunReport::new(parts);
+        let now = Instant::now();
+        let setup = (self.setup)(input);
+        if self.has_setup {
+            report.setup = Some(now.elapsed());
+        }
+        let setup = match setup {
+            Ok(setup) => setup,
+            Err(e) => {
+                report.failure = Some(Failure::new(Phase::Setup, e.as_ref()));
+                return report;
+            }
+        };
+        if let Some(part1) = self.part1.as_ref().filter(|_| parts.run_p1()) {
+            match time_part(|| part1(&setup)) {
+                Ok(p1) => report.part1 = Some(p1),
+                Err(e) => report.failure = Some(Failure::new(Phase::Part1, e.as_ref())),
+            }
+        }
+        if let Some(part2) = self.part2.as_ref().filter(|_| parts.run_p2()) {
+            match time_part(|| part2(setup)) {
+                Ok(p2) => report.part2 = Some(p2),
+                // The failure of part 1 is kept, as it came first
+                Err(e) => {
+                    report
+                        .failure
+                        .get_or_insert_with(|| Failure::new(Phase::Part2, e.as_ref()));
+                }
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_run_part() {
+        assert_eq!("1".parse(), Ok(RunPart::Part1));
+        assert_eq!("2".parse(), Ok(RunPart::Part2));
+        assert_eq!("all".parse(), Ok(RunPart::All));
+        assert!("12".parse::<RunPart>().is_err());
+        assert!("2x".parse::<RunPart>().is_err());
+        assert!("3".parse::<RunPart>().is_err());
+        assert!("".parse::<RunPart>().is_err());
+    }
+
+    /// An error with an optional source, to check that the whole chain is reported
+    #[derive(Debug)]
+    struct TestError(&'static str, Option<Box<TestError>>);
+    impl Display for TestError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+    impl Error for TestError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.1.as_deref().map(|e| e as _)
+        }
+    }
+
+    #[test]
+    fn failed_setup() {
+        let day = SolutionBuilder::try_setup(|_| {
+            Err::<u32, _>(TestError(
+                "Invalid input",
+                Some(Box::new(TestError("Line 1", None))),
+            ))
+        })
+        .part1(|n| n + 1)
+        .part2(|n| n + 2);
+        let report = day.run("", RunPart::All);
+        assert_eq!(
+            report.failure,
+            Some(Failure {
+                phase: Phase::Setup,
+                chain: vec!["Invalid input".to_string(), "Line 1".to_string()],
+            })
+        );
+        assert!(report.setup.is_some());
+        assert_eq!((report.part1, report.part2), (None, None));
+    }
+
+    #[test]
+    fn failed_parts() {
+        let day = SolutionBuilder::setup(|input: &str| input.len())
+            .try_part1(|_| Err::<usize, _>(TestError("Part 1 failed", None)))
+            .part2(|n| n * 2);
+        let report = day.run("abc", RunPart::All);
+        assert_eq!(report.part1, None);
+        assert_eq!(report.answer(2), Some("6"), "part 2 is still run");
+        assert_eq!(
+            report.failure.map(|f| (f.phase, f.chain)),
+            Some((Phase::Part1, vec!["Part 1 failed".to_string()]))
+        );
+
+        // Only the first failure is kept
+        let day = SolutionBuilder::try_part1(|_| Err::<u8, _>(TestError("Part 1 failed", None)))
+            .try_part2(|_| Err::<u8, _>(TestError("Part 2 failed", None)));
+        let report = day.run("", RunPart::All);
+        assert_eq!(report.failure.map(|f| f.phase), Some(Phase::Part1));
+        let report = day.run("", RunPart::Part2);
+        assert_eq!(
+            report.failure.map(|f| (f.phase, f.chain)),
+            Some((Phase::Part2, vec!["Part 2 failed".to_string()]))
+        );
+    }
+
+    #[test]
+    fn parts_written() {
+        fn none(_: &crate::Params) -> impl Solution<'static> {
+            SolutionBuilder::setup(|input: &str| input.len())
+        }
+        fn one(_: &crate::Params) -> impl Solution<'static> {
+            SolutionBuilder::part1(|input| input.len())
+        }
+        fn two(_: &crate::Params) -> impl Solution<'static> {
+            SolutionBuilder::try_setup(|input: &str| input.parse::<u8>())
+                .part1(|&n| n)
+                .try_part2(|n| n.checked_add(1).ok_or(TestError("Overflow", None)))
+        }
+        assert_eq!((parts(none), parts(one), parts(two)), (0, 1, 2));
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use aoc::{InputSource, RunPart};
+use chrono::Local;
+use serde::Deserialize;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Editors can write a file in several steps, so wait for them to finish before rebuilding
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// What to watch and run
+#[derive(Debug, Clone)]
+pub struct Watch {
+    pub year: u16,
+    pub day: u8,
+    pub parts: RunPart,
+    /// `--param` overrides, passed on to each run
+    pub params: Vec<(String, String)>,
+    /// Must not be [`InputSource::Stdin`]
+    pub input: InputSource,
+    /// Run the examples first, and only run the real input if they pass
+    pub examples: bool,
+}
+
+/// The subset of the `--format json` output which is shown
+#[derive(Debug, Deserialize)]
+struct JsonPart {
+    answer: String,
+    nanos: u64,
+}
+#[derive(Debug, Deserialize)]
+struct JsonFailure {
+    phase: String,
+    chain: Vec<String>,
+}
+#[derive(Debug, Deserialize)]
+struct JsonReport {
+    part1: Option<JsonPart>,
+    part2: Option<JsonPart>,
+    total_nanos: u64,
+    error: Option<JsonFailure>,
+}
+
+impl Watch {
+    /// The files which trigger a re-run when they change
+    fn files(&self) -> Vec<PathBuf> {
+        let source = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/src"))
+            .join(format!("y{}", self.year))
+            .join(format!("d{:02}.rs", self.day));
+        [Some(source), self.input.path(self.year, self.day)]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Rebuild and re-run the day whenever one of its files changes. Never returns
+    pub fn run(&self) -> ! {
+        let files = self.files();
+        let modified = || {
+            files
+                .iter()
+                .map(|f| fs::metadata(f).and_then(|m| m.modified()).ok())
+                .collect::<Vec<Option<SystemTime>>>()
+        };
+        let mut previous: Option<[Option<String>; 2]> = None;
+        let mut seen = None;
+        loop {
+            let current = modified();
+            if seen.as_ref() != Some(&current) {
+                thread::sleep(SETTLE_TIME);
+                seen = Some(modified());
+                self.run_once(&mut previous);
+                println!(
+                    "Watching {} for changes. Press Ctrl+C to stop",
+                    files
+                        .iter()
+                        .map(|f| f.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(" and ")
+                );
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// `cargo run` for this day, so that any changes are rebuilt
+    fn cargo(&self) -> Command {
+        let mut cmd = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+        cmd.args([
+            "run",
+            "--quiet",
+            "--manifest-path",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"),
+            "--features",
+            &format!("y{}", self.year),
+            "--",
+            &self.year.to_string(),
+            &self.day.to_string(),
+        ]);
+        match self.parts {
+            RunPart::All => (),
+            RunPart::Part1 => {
+                cmd.args(["-p", "1"]);
+            }
+            RunPart::Part2 => {
+                cmd.args(["-p", "2"]);
+            }
+        }
+        for (name, value) in &self.params {
+            cmd.arg("--param").arg(format!("{}={}", name, value));
+        }
+        cmd
+    }
+
+    fn run_once(&self, previous: &mut Option<[Option<String>; 2]>) {
+        println!(
+            "\n[{}] Running {} day {}",
+            Local::now().format("%H:%M:%S"),
+            self.year,
+            self.day
+        );
+        if self.examples {
+            match self.cargo().arg("--example").status() {
+                Ok(status) if status.success() => (),
+                Ok(_) => {
+                    println!("The examples failed, so the real input was not run");
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("Failed to run cargo: {}", e);
+                    return;
+                }
+            }
+            println!("Running the real input");
+        }
+        let mut cmd = self.cargo();
+        cmd.args(["--format", "json"]);
+        match &self.input {
+            InputSource::Dir(dir) => cmd.arg("--input-dir").arg(dir),
+            InputSource::File(file) => cmd.arg("--input").arg(file),
+            InputSource::Stdin => unreachable!("stdin cannot be watched"),
+        };
+        let output = match cmd.stderr(Stdio::inherit()).output() {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("Failed to run cargo: {}", e);
+                return;
+            }
+        };
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let Some(report) = stdout
+            .lines()
+            .find_map(|line| serde_json::from_str::<JsonReport>(line).ok())
+        else {
+            println!("The build or run failed ({})", output.status);
+            return;
+        };
+        let answers = [&report.part1, &report.part2].map(|p| p.as_ref().map(|p| p.answer.clone()));
+        for (i, part) in [&report.part1, &report.part2].into_iter().enumerate() {
+            let Some(part) = part else {
+                continue;
+            };
+            let change = match previous.as_ref().map(|p| &p[i]) {
+                None => String::new(),
+                Some(Some(prev)) if *prev == part.answer => " (unchanged)".to_string(),
+                Some(Some(prev)) if prev.contains('\n') || part.answer.contains('\n') => {
+                    format!("\n(changed from:\n{})", prev)
+                }
+                Some(Some(prev)) => format!(" (was {})", prev),
+                Some(None) => " (new)".to_string(),
+            };
+            println!(
+                "Part {}: {}{}. Completed in {:?}",
+                i + 1,
+                part.answer,
+                change,
+                Duration::from_nanos(part.nanos)
+            );
+        }
+        if let Some(failure) = &report.error {
+            println!(
+                "Error in {}: {}",
+                failure.phase,
+                failure.chain.join("\n  Caused by: ")
+            );
+        }
+        println!(
+            "Total duration: {:?}",
+            Duration::from_nanos(report.total_nanos)
+        );
+        *previous = Some(answers);
+    }
+}
@@ -1,5 +1,6 @@
+pub const TITLE: &str = "Calorie Counting";
+
 crate::aoc! {
-    include_str!("../../../input/2022/01.txt"),
     |i| i.split("\n\n").map(|s| {
         s.split('\n')
             .map(|n| n.parse::<u32>().expect("Error parsing u32"))
@@ -1,3 +1,5 @@
+pub const TITLE: &str = "Rock Paper Scissors";
+
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -123,7 +125,6 @@ impl FromStr for Round {
 }
 
 crate::aoc! {
-    include_str!("../../../input/2022/02.txt"),
     |input| input.split("\n").map(|s| s.parse::<Round>()).filter_map(|r| r.ok()),
     |rounds| rounds.clone().fold(0, |t, r| t + r.score1()),
     |rounds| rounds.fold(0, |t, r| t + r.score2())
@@ -1,3 +1,5 @@
+pub const TITLE: &str = "Rucksack Reorganization";
+
 fn priority(c: char) -> u8 {
     let ord = u8::try_from(c).unwrap();
     if ord >= 97 {
@@ -33,7 +35,6 @@ fn intersection_group<T: From<u8>>(s1: &str, s2: &str, s3: &str) -> T {
     0.into()
 }
 
-#[allow(dead_code)]
 const TEST_INPUT: &str = r"vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
 PmmdzqPrVvPwwTWBwg
@@ -41,8 +42,11 @@ wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
 
+crate::examples! {
+    "example" => TEST_INPUT => (157, 70),
+}
+
 crate::aoc! {
-    include_str!("../../../input/2022/03.txt"),
     |input| input.split("\n").map(intersection::<u16>).sum::<u16>(),
     |input| {
         let mut lines = input.split("\n");
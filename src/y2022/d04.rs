@@ -1,4 +1,5 @@
-#[allow(dead_code)]
+pub const TITLE: &str = "Camp Cleanup";
+
 const TEST_DATA: &str = r"2-4,6-8
 2-3,4-5
 5-7,7-9
@@ -6,12 +7,15 @@ const TEST_DATA: &str = r"2-4,6-8
 6-6,4-6
 2-6,4-8";
 
+crate::examples! {
+    "example" => TEST_DATA => (2, 4),
+}
+
 crate::aoc! {
-    include_str!("../../../input/2022/04.txt"),
     |input| input
         .split("\n")
         .filter_map(|p| {
-            if p.len() > 0 {
+            if !p.is_empty() {
                 let mut elves = p.split(",").map(|s| {
                     let (a, b) = s.split_once("-").unwrap();
                     a.parse::<u16>().unwrap()..=b.parse::<u16>().unwrap()
@@ -24,26 +28,16 @@ crate::aoc! {
     |pairs| pairs
         .clone()
         .filter(|pair| {
-            if (pair.0.contains(pair.1.start()) && pair.0.contains(pair.1.end()))
+            (pair.0.contains(pair.1.start()) && pair.0.contains(pair.1.end()))
                 || (pair.1.contains(pair.0.start()) && pair.1.contains(pair.0.end()))
-            {
-                true
-            } else {
-                false
-            }
         })
         .count(),
     |pairs| pairs
         .filter(|pair| {
-            if pair.0.contains(pair.1.start())
+            pair.0.contains(pair.1.start())
                 || pair.0.contains(pair.1.end())
                 || pair.1.contains(pair.0.start())
                 || pair.1.contains(pair.0.end())
-            {
-                true
-            } else {
-                false
-            }
         })
         .count(),
 }
@@ -1,8 +1,9 @@
-use std::str::FromStr;
+pub const TITLE: &str = "Supply Stacks";
 
-use fancy_regex::Regex;
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::parse::{self, ParseError, Span};
 
-#[allow(dead_code)]
 const TEST_INPUT: &str = r"    [D]    
 [N] [C]    
 [Z] [M] [P]
@@ -13,6 +14,10 @@ move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2";
 
+crate::examples! {
+    "example" => TEST_INPUT => ("CMZ", "MCD"),
+}
+
 #[derive(Debug, Clone)]
 struct Stacks<const N: usize>([Vec<char>; N]);
 impl<const N: usize> FromStr for Stacks<N> {
@@ -30,7 +35,7 @@ impl<const N: usize> FromStr for Stacks<N> {
         for line in s.rsplit("\n").skip(1) {
             let mut chars = line.chars();
             push_crate(chars.nth(1).unwrap_or(' '), &mut arr, 0);
-            for i in 1..=N {
+            for i in 1..N {
                 push_crate(chars.nth(3).unwrap_or(' '), &mut arr, i);
             }
         }
@@ -38,43 +43,55 @@ impl<const N: usize> FromStr for Stacks<N> {
     }
 }
 impl<const N: usize> Stacks<N> {
-    fn index(i: usize) -> usize {
-        if i < 1 || i > N {
-            panic!("Out of range: {} (1-{})", i, N);
-        } else {
-            i - 1
-        }
+    /// The stack numbered `i`, counting from 1
+    fn stack(&mut self, i: usize) -> Result<&mut Vec<char>, MoveError> {
+        (1..=N)
+            .contains(&i)
+            .then(|| &mut self.0[i - 1])
+            .ok_or(MoveError::NoStack(i, N))
     }
-    fn move_crate(&mut self, from: usize, to: usize) {
-        let c = self.0[Self::index(from)]
-            .pop()
-            .expect("Attempted to move crate from an empty stack");
-        self.0[Self::index(to)].push(c);
+    fn move_crate(&mut self, from: usize, to: usize) -> Result<(), MoveError> {
+        let c = self.stack(from)?.pop().ok_or(MoveError::Empty(from))?;
+        self.stack(to)?.push(c);
+        Ok(())
     }
-    fn move_crates(&mut self, inst: &Instruction) {
+    fn move_crates(&mut self, inst: &Instruction) -> Result<(), MoveError> {
         for _ in 0..inst.count {
-            self.move_crate(inst.from, inst.to);
+            self.move_crate(inst.from, inst.to)?;
         }
+        Ok(())
     }
-    fn move_crates_simultaneously(&mut self, inst: &Instruction) {
-        let mut c = Vec::new();
-        for _ in 0..inst.count {
-            c.push(
-                self.0[Self::index(inst.from)]
-                    .pop()
-                    .expect("Attempted to move crate from an empty stack"),
-            );
-        }
-        self.0[Self::index(inst.to)].extend(c.into_iter().rev());
+    fn move_crates_simultaneously(&mut self, inst: &Instruction) -> Result<(), MoveError> {
+        let from = self.stack(inst.from)?;
+        let start = from
+            .len()
+            .checked_sub(inst.count)
+            .ok_or(MoveError::Empty(inst.from))?;
+        let c = from.split_off(start);
+        self.stack(inst.to)?.extend(c);
+        Ok(())
     }
     fn top_crates(&self) -> String {
         self.0.iter().map(|v| v.last().unwrap_or(&' ')).collect()
     }
 }
 
-lazy_static::lazy_static! {
-    static ref INST_RE: Regex = Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
+#[derive(Debug)]
+enum MoveError {
+    /// The stack number, and the number of stacks
+    NoStack(usize, usize),
+    /// A move of more crates than the stack has
+    Empty(usize),
+}
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoStack(i, n) => write!(f, "There is no stack {} (the stacks are 1-{})", i, n),
+            Self::Empty(i) => write!(f, "Not enough crates on stack {} to move", i),
+        }
+    }
 }
+impl Error for MoveError {}
 
 #[derive(Debug)]
 struct Instruction {
@@ -82,41 +99,73 @@ struct Instruction {
     from: usize,
     to: usize,
 }
-impl FromStr for Instruction {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let caps = INST_RE.captures(s).map_err(|_| ())?.ok_or(())?;
-        Ok(Self {
-            count: caps.get(1).unwrap().as_str().parse().map_err(|_| ())?,
-            from: caps.get(2).unwrap().as_str().parse().map_err(|_| ())?,
-            to: caps.get(3).unwrap().as_str().parse().map_err(|_| ())?,
-        })
+impl Instruction {
+    fn parse(line: Span) -> Result<Self, ParseError> {
+        let (count, from, to) = line.template("move {count} from {stack} to {stack}")?;
+        Ok(Self { count, from, to })
     }
 }
 
-crate::aoc! {
-    include_str!("../../../input/2022/05.txt"),
-    |input| {
-        let (stacks, moves) = input.split_once("\n\n").unwrap();
-        let stacks: Stacks<9> = stacks.parse().unwrap();
-        let moves = moves
-            .split("\n")
-            .filter_map(|s| s.parse::<Instruction>().ok())
-            .collect::<Vec<_>>();
-        (stacks, moves)
-    },
-    |(stacks, moves)| {
-        let mut stacks = stacks.clone();
-        for inst in moves {
-            stacks.move_crates(inst);
+#[derive(Debug)]
+enum SetupError {
+    MissingMoves,
+    Stacks,
+    Instruction(ParseError),
+}
+impl fmt::Display for SetupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMoves => write!(
+                f,
+                "Expected a blank line between the stacks and the instructions"
+            ),
+            Self::Stacks => write!(f, "Failed to parse the stacks"),
+            Self::Instruction(_) => write!(f, "Invalid instruction"),
         }
-        stacks.top_crates()
-    },
-    |(mut stacks, moves)| {
-        for inst in &moves {
-            stacks.move_crates_simultaneously(inst);
+    }
+}
+impl Error for SetupError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Instruction(e) => Some(e),
+            _ => None,
         }
-        stacks.top_crates()
+    }
+}
+
+fn setup(input: &str) -> Result<(Stacks<9>, Vec<Instruction>), SetupError> {
+    let mut blocks = parse::blocks(input);
+    let stacks = blocks.next().ok_or(SetupError::Stacks)?;
+    let moves = blocks.next().ok_or(SetupError::MissingMoves)?;
+    let stacks = stacks.as_str().parse().map_err(|_| SetupError::Stacks)?;
+    let moves = moves
+        .lines()
+        .map(Instruction::parse)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(SetupError::Instruction)?;
+    Ok((stacks, moves))
+}
+
+fn part1((stacks, moves): &(Stacks<9>, Vec<Instruction>)) -> Result<String, MoveError> {
+    let mut stacks = stacks.clone();
+    for inst in moves {
+        stacks.move_crates(inst)?;
+    }
+    Ok(stacks.top_crates())
+}
+
+fn part2((mut stacks, moves): (Stacks<9>, Vec<Instruction>)) -> Result<String, MoveError> {
+    for inst in &moves {
+        stacks.move_crates_simultaneously(inst)?;
+    }
+    Ok(stacks.top_crates())
+}
+
+crate::aoc! {
+    solution: |_params| {
+        use crate::dispatcher::*;
+        DispatcherBuilder::try_setup(setup)
+            .try_part1(part1)
+            .try_part2(part2)
     },
 }
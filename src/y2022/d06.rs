@@ -1,3 +1,5 @@
+pub const TITLE: &str = "Tuning Trouble";
+
 use fancy_regex::Regex;
 
 lazy_static::lazy_static! {
@@ -6,7 +8,6 @@ lazy_static::lazy_static! {
 }
 
 crate::aoc! {
-    include_str!("../../../input/2022/06.txt"),
     |input|
         DIFF4_RE
             .find(input)
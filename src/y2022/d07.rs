@@ -1,3 +1,5 @@
+pub const TITLE: &str = "No Space Left On Device";
+
 use std::collections::HashMap;
 
 const ROOT: InodeName<'static> = InodeName(Vec::new());
@@ -44,7 +46,7 @@ impl<'s: 't, 't> FromIterator<&'t &'s str> for InodeName<'s> {
 }
 impl<'s> core::fmt::Display for InodeName<'s> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.0.len() > 0 {
+        if !self.0.is_empty() {
             write!(
                 f,
                 "{}",
@@ -117,7 +119,7 @@ impl<'s> LsItem<'s> {
     // fn path(&self, parent: &InodeName<'s>) -> InodeName<'s> {
     //     parent.with(self.name())
     // }
-    fn to_inode(self, parent: &InodeName<'s>) -> (InodeName<'s>, Inode) {
+    fn into_inode(self, parent: &InodeName<'s>) -> (InodeName<'s>, Inode) {
         match self {
             LsItem::Dir(n) => (parent.with(n), Inode::Dir),
             LsItem::File(n, s) => (parent.with(n), Inode::File(s)),
@@ -191,7 +193,7 @@ impl<'s> FSParser<'s> {
             Command::Cd(CdArg::Root) => self.current_path = ROOT,
             Command::Ls(contents) => {
                 for item in contents {
-                    let (path, inode) = item.to_inode(&self.current_path);
+                    let (path, inode) = item.into_inode(&self.current_path);
                     if self.files.contains_key(&path) {
                         eprintln!(
                             "Replacing inode \"{}\"!\n\tOld = {:?};\n\tNew = {:?};",
@@ -224,7 +226,6 @@ impl<'s> FSParser<'s> {
     }
 }
 
-#[allow(dead_code)]
 const TEST_INPUT: &str = r"$ cd /
 $ ls
 dir a
@@ -249,7 +250,11 @@ $ ls
 5626152 d.ext
 7214296 k";
 
-fn setup(input: &str) -> HashMap<InodeName, u32> {
+crate::examples! {
+    "example" => TEST_INPUT => (95437, 24933642),
+}
+
+fn setup(input: &str) -> HashMap<InodeName<'_>, u32> {
     input
         .split("$ ")
         .filter_map(|s| Command::try_from(s).ok())
@@ -267,13 +272,13 @@ fn part2(dirs: HashMap<InodeName, u32>) -> u32 {
     *dirs.values().filter(|&s| s >= &required).min().unwrap()
 }
 // crate::aoc!(
-//     include_str!("../../../input/2022/07.txt"),
 //     setup,
 //     part1,
 //     part2
 // );
-pub fn main(parts: crate::RunPart) {
-    use crate::dispatcher::*;
-    let dispatcher = DispatcherBuilder::setup(setup).part1(part1).part2(part2);
-    dispatcher.run(include_str!("../../../input/2022/07.txt"), parts);
+crate::aoc! {
+    solution: |_params| {
+        use crate::dispatcher::*;
+        DispatcherBuilder::setup(setup).part1(part1).part2(part2)
+    },
 }
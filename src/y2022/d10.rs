@@ -1,4 +1,5 @@
-#[allow(dead_code)]
+pub const TITLE: &str = "Cathode-Ray Tube";
+
 const TEST_INPUT: &str = r"addx 15
 addx -11
 addx 6
@@ -146,6 +147,10 @@ noop
 noop
 noop";
 
+crate::examples! {
+    "example" => TEST_INPUT => (13140, _),
+}
+
 fn display<I>(mut it: I) -> String
 where
     I: Iterator<Item = (i16, i16)>,
@@ -201,42 +206,44 @@ where
     display
 }
 
-pub fn main(parts: crate::RunPart) {
-    use crate::dispatcher::*;
-    DispatcherBuilder::setup(|input| {
-        input.split("\n").filter_map(|s| {
-            if s.len() < 4 {
-                None
-            } else {
-                match &s[0..4] {
-                    "addx" => Some((2, (&s[5..]).parse().unwrap())),
-                    "noop" => Some((1, 0i16)),
-                    _ => None,
+crate::aoc! {
+    // Passing `display` directly would require the input to be `'static`
+    #[allow(clippy::redundant_closure)]
+    solution: |_params| {
+        use crate::dispatcher::*;
+        DispatcherBuilder::setup(|input| {
+            input.split("\n").filter_map(|s| {
+                if s.len() < 4 {
+                    None
+                } else {
+                    match &s[0..4] {
+                        "addx" => Some((2, s[5..].parse().unwrap())),
+                        "noop" => Some((1, 0i16)),
+                        _ => None,
+                    }
                 }
-            }
+            })
         })
-    })
-    .part1(|input| {
-        let mut cycles = 0;
-        let mut x = 1i16;
-        let mut target = 20i16;
-        let mut answer = 0;
-        for (c, dx) in input.clone() {
-            let cyc = cycles + c;
-            if cyc >= target {
-                answer += x * target;
-                if target == 220 {
-                    break;
-                } else {
-                    target += 40;
+        .part1(|input| {
+            let mut cycles = 0;
+            let mut x = 1i16;
+            let mut target = 20i16;
+            let mut answer = 0;
+            for (c, dx) in input.clone() {
+                let cyc = cycles + c;
+                if cyc >= target {
+                    answer += x * target;
+                    if target == 220 {
+                        break;
+                    } else {
+                        target += 40;
+                    }
                 }
+                cycles = cyc;
+                x += dx;
             }
-            cycles = cyc;
-            x += dx;
-        }
-        answer
-    })
-    .part2(|input| display(input))
-    // .run(TEST_INPUT, parts);
-    .run(include_str!("../../../input/2022/10.txt"), parts);
+            answer
+        })
+        .part2(|input| display(input))
+    },
 }
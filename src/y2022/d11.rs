@@ -1,4 +1,15 @@
-use std::{collections::VecDeque, str::FromStr};
+pub const TITLE: &str = "Monkey in the Middle";
+
+use std::collections::VecDeque;
+
+use crate::{
+    parse::{self, ParseError, Span},
+    Param,
+};
+
+const PART1_ROUNDS: Param = Param::new("part1_rounds", "20", "The number of rounds in part 1");
+const PART2_ROUNDS: Param = Param::new("part2_rounds", "10000", "The number of rounds in part 2");
+pub const PARAMS: &[Param] = &[PART1_ROUNDS, PART2_ROUNDS];
 
 #[derive(Debug, Clone, Copy)]
 enum Op {
@@ -15,77 +26,33 @@ struct Monkey {
     true_index: usize,
     false_index: usize,
 }
-#[derive(Debug)]
-enum MonkeyParseError {
-    Int(std::num::ParseIntError, String),
-    Empty,
-    MissingItems(String),
-    MissingOp(String),
-    BadOp(String),
-    MissingTest(String),
-    MissingTrue(String),
-    MissingFalse(String),
-}
-impl FromStr for Monkey {
-    type Err = MonkeyParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use MonkeyParseError as E;
-        let mut iter = s.split("\n").map(|s| s.trim());
-        let mut line = iter.next().ok_or(E::Empty)?;
-        if line.starts_with("Monkey") {
-            line = iter.next().ok_or(E::Empty)?;
-        }
-        let items = if line.starts_with("Starting items: ") {
-            line[16..].split(", ").map(|i| i.parse().unwrap()).collect()
-        } else {
-            Err(E::MissingItems(line.to_string()))?
-        };
-        line = iter.next().ok_or(E::MissingOp(line.to_string()))?;
-        let op = if line.starts_with("Operation: new = old ") {
-            match line[21..].split_whitespace().collect::<Vec<_>>()[0..2] {
-                ["*", "old"] => Op::Sqr,
-                ["+", b] => Op::Add(b.parse::<u32>().map_err(|e| E::Int(e, line.to_string()))?),
-                ["*", b] => Op::Mul(b.parse::<u32>().map_err(|e| E::Int(e, line.to_string()))?),
-                _ => Err(E::BadOp(line.to_string()))?,
-            }
-        } else {
-            Err(E::MissingOp(line.to_string()))?
-        };
-        line = iter.next().ok_or(E::MissingTest(line.to_string()))?;
-        let divisor = if line.starts_with("Test: divisible by ") {
-            line[19..]
-                .parse()
-                .map_err(|e| E::Int(e, line.to_string()))?
-        } else {
-            Err(E::MissingTest(line.to_string()))?
-        };
-        line = iter.next().ok_or(E::MissingTrue(line.to_string()))?;
-        let true_index = if line.starts_with("If true: throw to monkey ") {
-            line[25..]
-                .parse()
-                .map_err(|e| E::Int(e, line.to_string()))?
-        } else {
-            Err(E::MissingTrue(line.to_string()))?
-        };
-        line = iter.next().ok_or(E::MissingFalse(line.to_string()))?;
-        let false_index = if line.starts_with("If false: throw to monkey ") {
-            line[26..]
-                .parse()
-                .map_err(|e| E::Int(e, line.to_string()))?
-        } else {
-            Err(E::MissingFalse(line.to_string()))?
-        };
-        Ok(Self {
-            items,
-            op,
-            divisor,
-            true_index,
-            false_index,
-        })
-    }
+fn parse_monkey(block: Span) -> Result<Monkey, ParseError> {
+    let mut lines = block.lines().map(Span::trim);
+    // A missing line is reported as the text it should start with being missing
+    let mut next = || lines.next().unwrap_or(block.end());
+    let _: (usize,) = next().template("Monkey {index}:")?;
+    let (items,) = next().template::<(Span,)>("Starting items: {items}")?;
+    let (operator, operand) =
+        next().template::<(Span, Span)>("Operation: new = old {operator} {operand}")?;
+    let op = match (operator.as_str(), operand.as_str()) {
+        ("*", "old") => Op::Sqr,
+        ("+", _) => Op::Add(operand.parse()?),
+        ("*", _) => Op::Mul(operand.parse()?),
+        _ => Err(operator.invalid("expected + or *"))?,
+    };
+    let (divisor,) = next().template("Test: divisible by {divisor}")?;
+    let (true_index,) = next().template("If true: throw to monkey {index}")?;
+    let (false_index,) = next().template("If false: throw to monkey {index}")?;
+    Ok(Monkey {
+        items: items.numbers()?.into(),
+        op,
+        divisor,
+        true_index,
+        false_index,
+    })
 }
 
+#[derive(Debug, Clone)]
 struct Monkeys {
     items: Vec<VecDeque<u32>>,
     monkeys: Vec<Monkey>,
@@ -105,7 +72,6 @@ impl FromIterator<Monkey> for Monkeys {
     }
 }
 
-#[allow(dead_code)]
 const TEST_INPUT: &str = r"Monkey 0:
 Starting items: 79, 98
 Operation: new = old * 19
@@ -134,19 +100,21 @@ Test: divisible by 17
   If true: throw to monkey 0
   If false: throw to monkey 1";
 
-fn run_monkeys(input: &str, num: usize, decrease_worry: bool) -> u32 {
-    let m = input
-        .split("\n\n")
-        .filter_map(|s| s.parse::<Monkey>().ok())
-        .collect::<Monkeys>();
+crate::examples! {
+    "example" => TEST_INPUT => (10605, _),
+    "one round" => TEST_INPUT => (_, 24) with { part2_rounds = 1 },
+}
+
+fn setup(input: &str) -> Result<Monkeys, ParseError> {
+    parse::blocks(input).map(parse_monkey).collect()
+}
+
+fn run_monkeys(m: Monkeys, num: usize, decrease_worry: bool) -> u32 {
     let mut monkeys: Vec<Monkey> = m.monkeys;
     let mut items = m.items;
     let l = monkeys.len();
 
-    let mut counts = Vec::new();
-    for _ in 0..l {
-        counts.push(0);
-    }
+    let mut counts = vec![0; l];
 
     for _ in 0..num {
         for i in 0..l {
@@ -181,10 +149,13 @@ fn run_monkeys(input: &str, num: usize, decrease_worry: bool) -> u32 {
     top_2.0 * top_2.1
 }
 
-pub fn main(parts: crate::RunPart) {
-    use crate::dispatcher::*;
-    DispatcherBuilder::part1(|input| run_monkeys(input, 20, true))
-        .part2(|input| run_monkeys(input, 10000, false))
-        // .run(TEST_INPUT, parts);
-        .run(include_str!("../../../input/2022/11.txt"), parts);
+crate::aoc! {
+    solution: |params| {
+        use crate::dispatcher::*;
+        let rounds1 = PART1_ROUNDS.get::<usize>(params);
+        let rounds2 = PART2_ROUNDS.get::<usize>(params);
+        DispatcherBuilder::try_setup(setup)
+            .try_part1(move |m| rounds1.clone().map(|n| run_monkeys(m.clone(), n, true)))
+            .try_part2(move |m| rounds2.clone().map(|n| run_monkeys(m, n, false)))
+    },
 }
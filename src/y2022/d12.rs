@@ -1,132 +1,109 @@
-use std::collections::HashSet;
+pub const TITLE: &str = "Hill Climbing Algorithm";
 
-type Point = (usize, usize);
+use std::{error::Error, fmt};
+
+use crate::{
+    geometry::Point,
+    grid::{Grid, GridError},
+    search,
+};
 
-#[allow(dead_code)]
 const TEST_INPUT: &str = r"Sabqponm
 abcryxxl
 accszExk
 acctuvwj
 abdefghi";
 
+crate::examples! {
+    "example" => TEST_INPUT => (31, 29),
+}
+
 #[derive(Debug)]
-struct Grid<'s> {
-    heightmap: Vec<&'s str>,
-    width: usize,
-    height: usize,
-    start: Point,
-    end: Point,
+struct Heightmap {
+    grid: Grid<char>,
+    start: Point<usize>,
+    end: Point<usize>,
 }
-impl<'s> Grid<'s> {
-    fn get(&self, (x, y): Point) -> char {
-        if y < self.heightmap.len() && x < self.heightmap[y].len() {
-            self.heightmap[y].chars().nth(x).unwrap()
-        } else {
-            panic!(
-                "Point ({}, {}) is outside the grid (width = {}, height = {})",
-                x,
-                y,
-                self.heightmap[0].len(),
-                self.heightmap.len()
-            )
+
+#[derive(Debug)]
+enum HeightmapError {
+    Grid(GridError),
+    Missing(char),
+}
+impl fmt::Display for HeightmapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Grid(_) => write!(f, "Invalid heightmap"),
+            Self::Missing(c) => write!(f, "The heightmap has no '{}'", c),
         }
     }
 }
-
-fn load_grid(input: &str) -> Grid {
-    let mut start = None;
-    let mut end = None;
-    let heightmap = input
-        .split_whitespace()
-        .enumerate()
-        .fold(Vec::new(), |mut h, (y, line)| {
-            if start.is_none() || end.is_none() {
-                for (x, c) in line
-                    .chars()
-                    .enumerate()
-                    .filter(|&(_, c)| c == 'S' || c == 'E')
-                {
-                    if c == 'S' {
-                        start = Some((x, y));
-                    } else if c == 'E' {
-                        end = Some((x, y));
-                    }
-                }
-            }
-            h.push(line);
-            h
-        });
-    let width = heightmap[0].len();
-    let height = heightmap.len();
-    Grid {
-        heightmap,
-        width,
-        height,
-        start: start.unwrap(),
-        end: end.unwrap(),
+impl Error for HeightmapError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Grid(e) => Some(e),
+            Self::Missing(_) => None,
+        }
     }
 }
 
-fn path_find(grid: &Grid, start: Point, end: char, valid_moves: fn(char, char) -> bool) -> u16 {
-    let mut steps = 0;
-    let mut processed = HashSet::new();
-    let mut to_process = HashSet::new();
-    to_process.insert(start);
-    'a: while !to_process.is_empty() {
-        let mut new = HashSet::new();
-        for point in to_process.drain() {
-            if grid.get(point) == end {
-                break 'a;
-            }
-            if processed.contains(&point) {
-                continue;
-            }
-            processed.insert(point);
-            new.extend(
-                available_moves(grid, point, valid_moves)
-                    .into_iter()
-                    .filter(|p| !processed.contains(p)),
-            );
-        }
-        steps += 1;
-        to_process = new;
-    }
-    steps
+fn load_grid(input: &str) -> Result<Heightmap, HeightmapError> {
+    let grid = Grid::parse_with(input.trim(), |c| {
+        (c.is_ascii_lowercase() || c == 'S' || c == 'E').then_some(c)
+    })
+    .map_err(HeightmapError::Grid)?;
+    let find = |c| grid.position(|&h| h == c).ok_or(HeightmapError::Missing(c));
+    Ok(Heightmap {
+        start: find('S')?,
+        end: find('E')?,
+        grid,
+    })
 }
 
-fn available_moves(grid: &Grid, (x, y): Point, valid: fn(char, char) -> bool) -> Vec<Point> {
-    let c = grid.get((x, y));
-    let mut v = Vec::new();
-    let mut f = |p| {
-        if valid(c, grid.get(p)) {
-            v.push(p);
-        }
-    };
-    if x > 0 {
-        f((x - 1, y));
-    }
-    if x < grid.width - 1 {
-        f((x + 1, y));
-    }
-    if y > 0 {
-        f((x, y - 1));
-    }
-    if y < grid.height - 1 {
-        f((x, y + 1));
+#[derive(Debug)]
+struct NoPath(char);
+impl fmt::Display for NoPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "There is no path to '{}'", self.0)
     }
-    v
+}
+impl Error for NoPath {}
+
+fn path_find(
+    grid: &Grid<char>,
+    start: Point<usize>,
+    end: char,
+    valid_moves: fn(char, char) -> bool,
+) -> Result<usize, NoPath> {
+    search::bfs(
+        [start],
+        |&pos| available_moves(grid, pos, valid_moves),
+        |&pos| grid[pos] == end,
+    )
+    .map(|path| path.cost)
+    .ok_or(NoPath(end))
+}
+
+fn available_moves(
+    grid: &Grid<char>,
+    pos: Point<usize>,
+    valid: fn(char, char) -> bool,
+) -> impl Iterator<Item = Point<usize>> + '_ {
+    let c = grid[pos];
+    grid.neighbours4(pos)
+        .filter(move |&p| valid(c, grid[p]))
 }
 
-fn part1(grid: &Grid) -> u16 {
-    path_find(grid, grid.start, 'E', |c, pc| {
+fn part1(map: &Heightmap) -> Result<usize, NoPath> {
+    path_find(&map.grid, map.start, 'E', |c, pc| {
         c == 'S' || (pc == 'E' && c >= 'y') || (pc != 'E' && pc <= ((c as u8) + 1) as char)
     })
 }
 
 // Tecnically a path using S could be the shortest path (with length == part1),
 // but I made the assumption that that would not be the case
-fn part2(grid: Grid) -> u16 {
-    path_find(&grid, grid.end, 'a', |c, pc| {
+fn part2(map: Heightmap) -> Result<usize, NoPath> {
+    path_find(&map.grid, map.end, 'a', |c, pc| {
         if c == 'E' {
             pc >= 'y'
         } else {
@@ -135,11 +112,11 @@ fn part2(grid: Grid) -> u16 {
     })
 }
 
-pub fn main(parts: crate::RunPart) {
-    use crate::dispatcher::*;
-    DispatcherBuilder::setup(load_grid)
-        .part1(part1)
-        .part2(part2)
-        // .run(TEST_INPUT, parts);
-        .run(include_str!("../../../input/2022/12.txt"), parts);
+crate::aoc! {
+    solution: |_params| {
+        use crate::dispatcher::*;
+        DispatcherBuilder::try_setup(load_grid)
+            .try_part1(part1)
+            .try_part2(part2)
+    },
 }
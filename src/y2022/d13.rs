@@ -1,3 +1,5 @@
+pub const TITLE: &str = "Distress Signal";
+
 use std::{cmp::Ordering, str::FromStr};
 
 use serde::Deserialize;
@@ -14,7 +16,7 @@ impl Value {
     fn values(&self) -> Vec<Self> {
         match self {
             Value::Num(n) => vec![Value::Num(*n)],
-            Value::List(v) => v.iter().cloned().collect(),
+            Value::List(v) => v.to_vec(),
         }
     }
 }
@@ -96,7 +98,6 @@ impl core::fmt::Debug for Value {
     }
 }
 
-#[allow(dead_code)]
 const TEST_INPUT: &str = r"[1,1,3,1,1]
 [1,1,5,1,1]
 
@@ -121,42 +122,46 @@ const TEST_INPUT: &str = r"[1,1,3,1,1]
 [1,[2,[3,[4,[5,6,7]]]],8,9]
 [1,[2,[3,[4,[5,6,0]]]],8,9]";
 
-pub fn main(parts: crate::RunPart) {
-    use crate::dispatcher::*;
-    DispatcherBuilder::setup(|input| {
-        input.split("\n\n").filter_map(|lines| {
-            lines
-                .split_once("\n")
-                .and_then(|(a, b)| Some((a.parse::<Value>().ok()?, b.parse::<Value>().ok()?)))
-        })
-    })
-    .part1(|pairs| {
-        pairs
-            .clone()
-            .enumerate()
-            .filter_map(|(i, (a, b))| if a <= b { Some(i + 1) } else { None })
-            .sum::<usize>()
-    })
-    .part2(|pairs| {
-        let div1: Value = "[[2]]".parse().unwrap();
-        let div2: Value = "[[6]]".parse().unwrap();
-        let mut packets = pairs
-            .flat_map(|(a, b)| [a, b])
-            .chain([div1.clone(), div2.clone()])
-            .collect::<Vec<_>>();
-        packets.sort();
-        packets
-            .into_iter()
-            .enumerate()
-            .filter_map(|(i, v)| {
-                if v == div1 || v == div2 {
-                    Some(i + 1)
-                } else {
-                    None
-                }
+crate::examples! {
+    "example" => TEST_INPUT => (13, 140),
+}
+
+crate::aoc! {
+    solution: |_params| {
+        use crate::dispatcher::*;
+        DispatcherBuilder::setup(|input| {
+            input.split("\n\n").filter_map(|lines| {
+                lines
+                    .split_once("\n")
+                    .and_then(|(a, b)| Some((a.parse::<Value>().ok()?, b.parse::<Value>().ok()?)))
             })
-            .product::<usize>()
-    })
-    // .run(TEST_INPUT, parts);
-    .run(include_str!("../../../input/2022/13.txt"), parts);
+        })
+        .part1(|pairs| {
+            pairs
+                .clone()
+                .enumerate()
+                .filter_map(|(i, (a, b))| if a <= b { Some(i + 1) } else { None })
+                .sum::<usize>()
+        })
+        .part2(|pairs| {
+            let div1: Value = "[[2]]".parse().unwrap();
+            let div2: Value = "[[6]]".parse().unwrap();
+            let mut packets = pairs
+                .flat_map(|(a, b)| [a, b])
+                .chain([div1.clone(), div2.clone()])
+                .collect::<Vec<_>>();
+            packets.sort();
+            packets
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, v)| {
+                    if v == div1 || v == div2 {
+                        Some(i + 1)
+                    } else {
+                        None
+                    }
+                })
+                .product::<usize>()
+        })
+    },
 }
@@ -1,3 +1,5 @@
+pub const TITLE: &str = "Regolith Reservoir";
+
 use std::{cmp, collections::HashMap, str::FromStr};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -161,38 +163,41 @@ impl core::fmt::Display for Grid {
                     }
                 })?;
             }
-            writeln!(f, "")?;
+            writeln!(f)?;
         }
         Ok(())
     }
 }
 
-#[allow(dead_code)]
 const TEST_INPUT: &str = r"498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9";
 
-pub fn main(parts: crate::RunPart) {
-    use crate::dispatcher::*;
-    DispatcherBuilder::setup(|input| input.parse::<Grid>().unwrap())
-        .part1(|grid| {
-            // println!("{}", grid);
-            let mut grid = grid.clone();
-            let mut count = 0;
-            while grid.drop_sand(false).is_some() {
-                // println!("Grid:\n{}", grid);
-                count += 1;
-            }
-            count
-        })
-        .part2(|mut grid| {
-            // println!("{:#}", grid);
-            let mut count = 0;
-            while grid.drop_sand(true).is_some() {
-                // println!("Grid:\n{:#}", grid);
-                count += 1;
-            }
-            count
-        })
-        // .run(TEST_INPUT, parts);
-        .run(include_str!("../../../input/2022/14.txt"), parts);
+crate::examples! {
+    "example" => TEST_INPUT => (24, 93),
+}
+
+crate::aoc! {
+    solution: |_params| {
+        use crate::dispatcher::*;
+        DispatcherBuilder::setup(|input| input.parse::<Grid>().unwrap())
+            .part1(|grid| {
+                // println!("{}", grid);
+                let mut grid = grid.clone();
+                let mut count = 0;
+                while grid.drop_sand(false).is_some() {
+                    // println!("Grid:\n{}", grid);
+                    count += 1;
+                }
+                count
+            })
+            .part2(|mut grid| {
+                // println!("{:#}", grid);
+                let mut count = 0;
+                while grid.drop_sand(true).is_some() {
+                    // println!("Grid:\n{:#}", grid);
+                    count += 1;
+                }
+                count
+            })
+    },
 }
@@ -1,12 +1,27 @@
-use std::collections::{HashMap, HashSet};
+pub const TITLE: &str = "Beacon Exclusion Zone";
 
-use fancy_regex::Regex;
+use std::{collections::HashSet, error::Error, fmt, ops::Range};
 
-lazy_static::lazy_static! {
-    static ref RE: Regex = Regex::new(r"Sensor at x=(-?\d+), y=(-?\d+): .+ x=(-?\d+), y=(-?\d+)").unwrap();
-}
+use crate::{
+    geometry::Point,
+    params::ParamError,
+    parse::{self, ParseError, Span},
+    ranges::RangeSet,
+    Param,
+};
+
+const ROW: Param = Param::new(
+    "row",
+    "2000000",
+    "The row to count the positions which cannot contain a beacon in",
+);
+const MAX: Param = Param::new(
+    "max",
+    "4000000",
+    "The highest x and y coordinate the distress beacon can be at",
+);
+pub const PARAMS: &[Param] = &[ROW, MAX];
 
-#[allow(dead_code)]
 const TEST_INPUT: &str = r"Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
 Sensor at x=13, y=2: closest beacon is at x=15, y=3
@@ -22,136 +37,113 @@ Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
-type Point = (i64, i64);
+crate::examples! {
+    "example" => TEST_INPUT => (26, 56000011) with { row = 10, max = 20 },
+}
 
 #[derive(Debug)]
 struct Sensor {
-    x: i64,
-    y: i64,
+    pos: Point<i64>,
+    /// The distance to the closest beacon
     d: i64,
-    occlusions: HashMap<i64, (i64, i64)>,
 }
 impl Sensor {
-    fn new((s_x, s_y): Point, (b_x, b_y): Point) -> Self {
+    fn new(pos: Point<i64>, beacon: Point<i64>) -> Self {
         Self {
-            x: s_x,
-            y: s_y,
-            d: (b_x - s_x).abs() + (b_y - s_y).abs(),
-            occlusions: HashMap::new(),
+            pos,
+            d: pos.manhattan(beacon),
         }
     }
-    fn calc_occlusions(&mut self) {
-        for y in self.y - self.d..=self.y + self.d {
-            self.occlusions.insert(y, self.occlusion(y).unwrap());
-        }
-    }
-    fn occlusion(&self, y: i64) -> Option<(i64, i64)> {
-        self.occlusions.get(&y).copied().or_else(|| {
-            let dy = (y - self.y).abs();
-            if dy < self.d {
-                let dx = (self.d - dy).abs();
-                Some((self.x - dx, self.x + dx))
-            } else if dy == self.d {
-                Some((self.x, self.x))
-            } else {
-                None
-            }
-        })
-    }
-    fn min_x(&self, x: i64) -> i64 {
-        std::cmp::min(self.x - self.d, x)
-    }
-    fn max_x(&self, x: i64) -> i64 {
-        std::cmp::max(self.x - self.d, x)
+    /// The positions in row `y` which are no further from the sensor than its beacon
+    fn occlusion(&self, y: i64) -> Option<Range<i64>> {
+        let dx = self.d - (y - self.pos.y).abs();
+        (dx >= 0).then(|| self.pos.x - dx..self.pos.x + dx + 1)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct Grid {
     sensors: Vec<Sensor>,
-    beacons: HashSet<Point>,
-    left: i64,
-    right: i64,
+    beacons: HashSet<Point<i64>>,
 }
 impl Grid {
-    fn new() -> Self {
-        Self {
-            sensors: Vec::new(),
-            beacons: HashSet::new(),
-            left: i64::MAX,
-            right: i64::MIN,
-        }
+    /// The positions in row `y` which cannot contain an unknown beacon
+    fn coverage(&self, y: i64) -> RangeSet<i64> {
+        self.sensors.iter().filter_map(|s| s.occlusion(y)).collect()
     }
 }
 
-fn setup(input: &str) -> Grid {
-    input
-        .split("\n")
-        .filter_map(|s| {
-            RE.captures(s).unwrap().map(|caps| {
-                let x: i64 = caps.get(1).unwrap().as_str().parse().unwrap();
-                let y: i64 = caps.get(2).unwrap().as_str().parse().unwrap();
-                let b_x: i64 = caps.get(3).unwrap().as_str().parse().unwrap();
-                let b_y: i64 = caps.get(4).unwrap().as_str().parse().unwrap();
-                (Sensor::new((x, y), (b_x, b_y)), (b_x, b_y))
-            })
-        })
-        .fold(Grid::new(), |mut g, (mut s, b)| {
+fn parse_sensor(line: Span) -> Result<(Sensor, Point<i64>), ParseError> {
+    let (x, y, b_x, b_y) =
+        line.template("Sensor at x={x}, y={y}: closest beacon is at x={x}, y={y}")?;
+    let (sensor, beacon) = (Point::new(x, y), Point::new(b_x, b_y));
+    Ok((Sensor::new(sensor, beacon), beacon))
+}
+
+fn setup(input: &str) -> Result<Grid, ParseError> {
+    parse::lines(input)
+        .map(parse_sensor)
+        .try_fold(Grid::default(), |mut g, sensor| {
+            let (s, b) = sensor?;
             g.beacons.insert(b);
-            g.left = s.min_x(g.left);
-            g.right = s.max_x(g.right);
-            s.calc_occlusions();
             g.sensors.push(s);
-            g
+            Ok(g)
         })
 }
 
-fn part1(grid: &Grid) -> usize {
-    let y = 2_000_000;
-    let points = grid
-        .sensors
-        .iter()
-        .filter_map(|s| s.occlusion(y).map(|(x0, x1)| x0..=x1))
-        .flatten()
-        .collect::<HashSet<_>>();
-    points.len() - grid.beacons.iter().filter(|&&(_, b_y)| b_y == y).count()
+fn part1(grid: &Grid, y: i64) -> i64 {
+    // Every beacon is covered by the sensor it is closest to
+    let beacons = grid.beacons.iter().filter(|b| b.y == y).count() as i64;
+    grid.coverage(y).len() - beacons
 }
 
-fn part2(grid: Grid) -> i64 {
-    for y in 0..=4_000_000 {
-        let mut x = 0;
-        let mut sensors = grid
-            .sensors
-            .iter()
-            .filter_map(|s| s.occlusion(y))
-            .collect::<Vec<_>>();
-        sensors.sort();
-        for (sx0, sx1) in sensors {
-            if grid.beacons.contains(&(x, y)) {
-                x += 1;
-            }
-            if sx1 < x {
-                continue;
-            } else if sx0 < x {
-                x = sx1 + 1;
-                if x > 4_000_000 {
-                    break;
-                } else {
-                    continue;
-                }
-            } else {
-                return x * 4_000_000 + y;
-            }
+#[derive(Debug)]
+enum Part2Error {
+    Param(ParamError),
+    /// Every position up to the maximum coordinate is covered by a sensor
+    NoGap(i64),
+}
+impl fmt::Display for Part2Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Param(_) => write!(f, "Invalid parameter"),
+            Self::NoGap(max) => write!(
+                f,
+                "Every position from 0 to {} is covered by a sensor",
+                max
+            ),
+        }
+    }
+}
+impl Error for Part2Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Param(e) => Some(e),
+            Self::NoGap(_) => None,
         }
     }
-    todo!()
 }
 
-pub fn main(parts: crate::RunPart) {
-    use crate::dispatcher::*;
-    DispatcherBuilder::setup(setup)
-        .part1(part1)
-        .part2(part2)
-        // .run(TEST_INPUT, parts);
-        .run(include_str!("../../../input/2022/15.txt"), parts);
+fn part2(grid: Grid, max: i64) -> Result<i64, Part2Error> {
+    let area = RangeSet::from(0..max + 1);
+    (0..=max)
+        .find_map(|y| {
+            let x = area.difference(&grid.coverage(y)).min()?;
+            Some(x * 4_000_000 + y)
+        })
+        .ok_or(Part2Error::NoGap(max))
+}
+
+crate::aoc! {
+    solution: |params| {
+        use crate::dispatcher::*;
+        let (row, max) = (ROW.get::<i64>(params), MAX.get::<i64>(params));
+        DispatcherBuilder::try_setup(setup)
+            .try_part1(move |grid| row.clone().map(|row| part1(grid, row)))
+            .try_part2(move |grid| {
+                max.clone()
+                    .map_err(Part2Error::Param)
+                    .and_then(|max| part2(grid, max))
+            })
+    },
 }
@@ -1,3 +1,5 @@
+pub const TITLE: &str = "Trebuchet?!";
+
 use logos::{Lexer, Logos};
 
 macro_rules! make_enum {
@@ -69,8 +71,7 @@ impl Num {
 }
 
 crate::aoc! {
-    include_str!("../../../input/2023/01.txt"),
-    |i| i.split("\n").filter(|s| s.len() > 0).map(|s| {
+    |i| i.split("\n").filter(|s| !s.is_empty()).map(|s| {
         fn find<'s>(mut lex: Lexer<'s, Number>) -> Num {
             let mut short: Option<u32> = None;
             let mut long: Option<u32> = None;
@@ -1,6 +1,8 @@
+pub const TITLE: &str = "Cube Conundrum";
+
 use std::cmp;
 
-use fancy_regex::Regex;
+use crate::parse::{self, ParseError, Span};
 
 #[derive(Debug)]
 struct Game {
@@ -25,32 +27,35 @@ impl Game {
     }
 }
 
-lazy_static::lazy_static! {
-    static ref RED_RE: Regex = Regex::new(r"(\d+) red").unwrap();
-    static ref GREEN_RE: Regex = Regex::new(r"(\d+) green").unwrap();
-    static ref BLUE_RE: Regex = Regex::new(r"(\d+) blue").unwrap();
+fn parse_game(line: Span) -> Result<Game, ParseError> {
+    let (id, views) = line.template::<(u16, Span)>("Game {id}: {views}")?;
+    let mut game = Game::new(id);
+    for view in views.split(";") {
+        let (mut red, mut green, mut blue) = (0, 0, 0);
+        for cubes in view.split(",") {
+            let (count, colour) = cubes.trim().template::<(u8, Span)>("{count} {colour}")?;
+            match colour.as_str() {
+                "red" => red = count,
+                "green" => green = count,
+                "blue" => blue = count,
+                _ => Err(colour.invalid("expected red, green or blue"))?,
+            }
+        }
+        game.add_counter_view(red, green, blue);
+    }
+    Ok(game)
+}
+
+crate::examples! {
+    "example" => r"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green" => (8, 2286),
 }
 
 crate::aoc! {
-    include_str!("../../../input/2023/02.txt"),
-//     r"
-// Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-// Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-// Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-// Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-// Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
-    |i| i.split("\n").filter(|s| s.len() > 0).map(|s| {
-        let (gs, cs) = s.split_once(":").unwrap();
-        let mut game = Game::new(gs[5..].parse().unwrap());
-        for view in cs.split(";") {
-            game.add_counter_view(
-                RED_RE.captures(view).unwrap().map(|c| c.get(1).unwrap().as_str().parse().unwrap()).unwrap_or_default(),
-                GREEN_RE.captures(view).unwrap().map(|c| c.get(1).unwrap().as_str().parse().unwrap()).unwrap_or_default(),
-                BLUE_RE.captures(view).unwrap().map(|c| c.get(1).unwrap().as_str().parse().unwrap()).unwrap_or_default(),
-            );
-        }
-        game
-    }).collect::<Vec<_>>(),
-    |data| data.iter().filter_map(|g| if g.max_r <= 12 && g.max_g <= 13 && g.max_b <= 14 { Some(g.id) } else { None }).sum::<u16>(),
-    |data| data.iter().map(|g| u32::from(g.max_r) * u32::from(g.max_g) * u32::from(g.max_b)).sum::<u32>(),
+    try_setup: |i| parse::lines(i).map(parse_game).collect::<Result<Vec<_>, _>>(),
+    part1: |data| data.iter().filter_map(|g| if g.max_r <= 12 && g.max_g <= 13 && g.max_b <= 14 { Some(g.id) } else { None }).sum::<u16>(),
+    part2: |data| data.iter().map(|g| u32::from(g.max_r) * u32::from(g.max_g) * u32::from(g.max_b)).sum::<u32>(),
 }
@@ -1,134 +1,144 @@
-use std::cmp;
+pub const TITLE: &str = "If You Give A Seed A Fertilizer";
+
+use std::{error::Error, fmt};
+
+use crate::{
+    parse::{self, ParseError, Span},
+    ranges::{RangeMap, RangeSet},
+};
 
 type Id = u64;
 
-fn part1(seeds: &Vec<Id>, maps: &[Vec<(Id, Id, Id)>; 7]) -> Id {
+#[derive(Debug)]
+enum SetupError {
+    Empty,
+    /// The number of maps found, if it is not 7
+    MapCount(usize),
+    Parse(ParseError),
+}
+impl fmt::Display for SetupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "The input is empty"),
+            Self::MapCount(n) => write!(f, "Expected 7 maps, found {}", n),
+            Self::Parse(_) => write!(f, "Invalid almanac"),
+        }
+    }
+}
+impl Error for SetupError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+impl From<ParseError> for SetupError {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+/// A map block: a header line, followed by a `destination source length` line for each range
+fn parse_map(block: Span) -> Result<RangeMap<Id>, ParseError> {
+    let mut map = RangeMap::default();
+    for line in block.lines().skip(1) {
+        let (dst_start, src_start, len): (Id, Id, Id) = line.template("{dst} {src} {len}")?;
+        map.insert(src_start..src_start + len, dst_start);
+    }
+    Ok(map)
+}
+
+fn setup(input: &str) -> Result<(Vec<Id>, [RangeMap<Id>; 7]), SetupError> {
+    let mut blocks = parse::blocks(input);
+    let (_, seeds) = blocks.next().ok_or(SetupError::Empty)?.key_value()?;
+    let seeds = seeds.numbers()?;
+    let maps = blocks.map(parse_map).collect::<Result<Vec<_>, _>>()?;
+    let maps = maps
+        .try_into()
+        .map_err(|maps: Vec<_>| SetupError::MapCount(maps.len()))?;
+    Ok((seeds, maps))
+}
+
+#[derive(Debug)]
+enum SeedError {
+    NoSeeds,
+    /// Part 2 reads the seed numbers as pairs of start and length
+    Unpaired,
+}
+impl fmt::Display for SeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSeeds => write!(f, "There are no seeds"),
+            Self::Unpaired => write!(f, "The last seed range has no length"),
+        }
+    }
+}
+impl Error for SeedError {}
+
+fn part1((seeds, maps): &(Vec<Id>, [RangeMap<Id>; 7])) -> Result<Id, SeedError> {
     seeds
         .iter()
-        .map(|seed| {
-            maps.iter().fold(*seed, |id, map| {
-                if let Some((dst_start, src_start, _)) = map
-                    .iter()
-                    .find(|(_, src_start, len)| src_start <= &id && src_start + len > id)
-                {
-                    dst_start + id - src_start
-                } else {
-                    id
-                }
-            })
-        })
-        .reduce(|a, b| cmp::min(a, b))
-        .unwrap()
+        .map(|&seed| maps.iter().fold(seed, |id, map| map.get(id)))
+        .min()
+        .ok_or(SeedError::NoSeeds)
 }
 
-fn part2(seeds: Vec<Id>, maps: [Vec<(Id, Id, Id)>; 7]) -> Id {
-    let mut ids: Vec<_> = seeds
-        .chunks(2)
-        .map(|pair| (pair[0], pair[0] + pair[1]))
-        .collect();
-    for map in maps {
-        ids = ids
-            .into_iter()
-            .flat_map(|(start, end)| {
-                let (remaining, processed) = map.iter().fold(
-                    (vec![(start, end)], Vec::new()),
-                    |(remaining, mut processed), &(dst_start, src_start, src_len)| {
-                        let mut rem = Vec::new();
-                        let mut any_proc = false;
-                        dbg!((dst_start, src_start, src_len), &remaining);
-                        for (start, end) in remaining.iter().cloned() {
-                            if src_start > end || start > src_start + src_len {
-                                continue;
-                            }
-                            any_proc = true;
-                            let src_end = src_start + src_len;
-                            let dst_end = dst_start + src_len;
-                            match dbg!((start < src_start, end > src_end)) {
-                                (true, true) => {
-                                    processed.push((dst_start, dst_end));
-                                    rem.push((start, src_start));
-                                    rem.push((src_end, end));
-                                }
-                                (true, false) => {
-                                    processed.push((dst_start, dst_start + src_start - start));
-                                    rem.push((start, src_start));
-                                }
-                                (false, true) => {
-                                    processed.push((dst_start + start - src_start, dst_end));
-                                    rem.push((src_end, end));
-                                }
-                                (false, false) => {
-                                    processed.push((
-                                        dst_start + start - src_start,
-                                        dst_start + end - src_start,
-                                    ));
-                                }
-                            }
-                        }
-                        dbg!((if any_proc { rem } else { remaining }, processed))
-                    },
-                );
-                dbg!(processed.into_iter().chain(remaining.into_iter()))
-            })
-            .collect();
+fn part2((seeds, maps): (Vec<Id>, [RangeMap<Id>; 7])) -> Result<Id, SeedError> {
+    if seeds.len() % 2 != 0 {
+        return Err(SeedError::Unpaired);
     }
-    ids.into_iter()
-        .reduce(|(a, _), (b, _)| (cmp::min(a, b), 0))
-        .unwrap()
-        .0
+    let seeds = seeds
+        .chunks(2)
+        .map(|pair| pair[0]..pair[0] + pair[1])
+        .collect::<RangeSet<_>>();
+    maps.iter()
+        .fold(seeds, |ids, map| map.map_set(&ids))
+        .min()
+        .ok_or(SeedError::NoSeeds)
+}
+
+crate::examples! {
+    "example" => r"seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4" => (35, 46),
 }
 
 crate::aoc! {
-    include_str!("../../../input/2023/05.txt"),
-//     r"
-// seeds: 79 14 55 13
-
-// seed-to-soil map:
-// 50 98 2
-// 52 50 48
-
-// soil-to-fertilizer map:
-// 0 15 37
-// 37 52 2
-// 39 0 15
-
-// fertilizer-to-water map:
-// 49 53 8
-// 0 11 42
-// 42 0 7
-// 57 7 4
-
-// water-to-light map:
-// 88 18 7
-// 18 25 70
-
-// light-to-temperature map:
-// 45 77 23
-// 81 45 19
-// 68 64 13
-
-// temperature-to-humidity map:
-// 0 69 1
-// 1 0 69
-
-// humidity-to-location map:
-// 60 56 37
-// 56 93 4",
-    |i| {
-        let mut map_inputs = i.split("\n\n");
-        let seeds = map_inputs.next().unwrap()[7..].trim().split(" ").map(|s| s.parse::<Id>().unwrap()).collect::<Vec<_>>();
-        let mut maps: [Vec<(Id, Id, Id)>; 7] = [Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new()];
-        for (i, input) in map_inputs.enumerate() {
-            for line in input.split("\n").skip(1).filter(|l| !l.is_empty()) {
-                let mut nums = line.split(" ").map(|s| s.parse::<Id>().unwrap());
-                let dst_start = nums.next().unwrap();
-                let src_start = nums.next().unwrap();
-                let len = nums.next().unwrap();
-                maps[i].push((dst_start, src_start, len));
-            }
-        }
-        (seeds, maps)
+    solution: |_params| {
+        use crate::dispatcher::*;
+        DispatcherBuilder::try_setup(setup)
+            .try_part1(part1)
+            .try_part2(part2)
     },
-    |(seeds, maps)| part1(seeds, maps),
-    |(seeds, maps)| part2(seeds, maps),
 }
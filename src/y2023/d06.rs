@@ -1,12 +1,15 @@
+pub const TITLE: &str = "Wait For It";
+
 use std::iter;
 
 use regex::Regex;
 
+crate::examples! {
+    "example" => r"Time:      7  15   30
+Distance:  9  40  200" => (288, 71503),
+}
+
 crate::aoc! {
-    include_str!("../../../input/2023/06.txt"),
-//     r"
-// Time:      7  15   30
-// Distance:  9  40  200",
     |i| {
         let re = Regex::new(r"\s+").unwrap();
         let (time, record) = i.trim().split_once("\n").unwrap();
@@ -1,3 +1,5 @@
+pub const TITLE: &str = "Camel Cards";
+
 use std::{cmp, collections::HashMap};
 
 macro_rules! make_cards {
@@ -128,15 +130,16 @@ impl Hand {
     }
 }
 
+crate::examples! {
+    "example" => r"32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483" => (6440, 5905),
+}
+
 crate::aoc! {
-    include_str!("../../../input/2023/07.txt"),
-//     r"
-// 32T3K 765
-// T55J5 684
-// KK677 28
-// KTJJT 220
-// QQQJA 483",
-    |i| i.split("\n").filter(|s| s.len() > 0).map(|s| {
+    |i| i.split("\n").filter(|s| !s.is_empty()).map(|s| {
         let (cards, bid) = s.split_once(" ").unwrap();
         let mut cards = cards.chars().map(|c| Card::try_from(c).unwrap());
         let cards = [
@@ -1,16 +1,31 @@
+pub const TITLE: &str = "Haunted Wasteland";
+
 use std::collections::HashMap;
 
 use fancy_regex::Regex;
 
+crate::examples! {
+    "example" => r"RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)" => (2, _),
+    "repeated instructions" => r"LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)" => (6, _),
+}
+
 crate::aoc! {
-    include_str!("../../../input/2023/08.txt"),
-//     r"
-// Time:      7  15   30
-// Distance:  9  40  200",
     |i| {
         let (instructions, tree) = i.trim().split_once("\n\n").unwrap();
         let re = Regex::new(r"(?P<parent>[A-Z]{3}) = \((?P<left>[A-Z]{3}), (?P<right>[A-Z]{3})\)").unwrap();
-        let tree = tree.split("\n").filter_map(|s| if s.trim().len() > 0 {
+        let tree = tree.split("\n").filter_map(|s| if !s.trim().is_empty() {
             let caps = re.captures(s).unwrap().expect("Line does not match regex");
             let parent = caps.name("parent").unwrap().as_str();
             let left = caps.name("left").unwrap().as_str();
@@ -36,9 +51,9 @@ crate::aoc! {
     },
     |(instructions, tree)| {
         let mut instructions = instructions.chars().cycle();
-        let mut nodes = tree.keys().filter(|s| s.chars().last().unwrap() == 'A').cloned().collect::<Vec<_>>();
+        let mut nodes = tree.keys().filter(|s| s.ends_with('A')).cloned().collect::<Vec<_>>();
         let mut count = 0usize;
-        while !nodes.iter().all(|s| s.chars().last().unwrap() == 'Z') {
+        while !nodes.iter().all(|s| s.ends_with('Z')) {
             count += 1;
             let inst = instructions.next().unwrap();
             nodes = nodes.into_iter().map(|node| if inst == 'L' { tree.get(node).unwrap().0 } else { tree.get(node).unwrap().1 }).collect();
@@ -1,5 +1,8 @@
+pub const TITLE: &str = "Mirage Maintenance";
+
 #[derive(Debug)]
 struct Series {
+    #[allow(dead_code)]
     sequence: Vec<i64>,
     next: i64,
     prev: i64,
@@ -41,13 +44,14 @@ impl Series {
     }
 }
 
+crate::examples! {
+    "example" => r"0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45" => (114, 2),
+}
+
 crate::aoc! {
-    include_str!("../../../input/2023/09.txt"),
-//     r"
-// 0 3 6 9 12 15
-// 1 3 6 10 15 21
-// 10 13 16 21 30 45",
-    |i| i.split("\n").filter(|s| s.len() > 0).map(|s| Series::new(s.split(" ").map(|n| n.parse::<i64>().unwrap()).collect::<Vec<_>>())).collect::<Vec<_>>(),
+    |i| i.split("\n").filter(|s| !s.is_empty()).map(|s| Series::new(s.split(" ").map(|n| n.parse::<i64>().unwrap()).collect::<Vec<_>>())).collect::<Vec<_>>(),
     |data| data.iter().fold(0, |acc, s| acc + s.next),
     |data| data.iter().fold(0, |acc, s| acc + s.prev),
 }
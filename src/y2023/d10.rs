@@ -1,14 +1,31 @@
-use std::collections::{HashMap, HashSet};
+pub const TITLE: &str = "Pipe Maze";
 
-/// (0,0) is at top left
-#[derive(Debug, Default)]
-struct Grid {
-    width: usize,
-    height: usize,
-    map: HashMap<(usize, usize), Pipe>,
-    start: Option<(usize, usize)>,
+use std::{error::Error, fmt};
+
+use crate::{
+    geometry::{Dir4, Point},
+    grid::{Grid, GridError},
+    search,
+};
+
+#[derive(Debug)]
+struct Maze {
+    grid: Grid<Pipe>,
+    start: Point<usize>,
+}
+impl Maze {
+    /// The positions of the pipes which connect to the pipe at `pos`
+    fn connected(&self, pos: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        let pipe = self.grid[pos];
+        Dir4::ALL
+            .into_iter()
+            .filter(move |&d| pipe.connect(d))
+            .filter_map(move |d| {
+                let next = pos.checked_step(d)?;
+                self.grid.get(next)?.connect(d.opposite()).then_some(next)
+            })
+    }
 }
-impl Grid {}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Pipe {
@@ -29,9 +46,10 @@ enum Pipe {
     /// S is the starting position of the animal; there is a pipe on this
     Start,
 }
-impl From<char> for Pipe {
-    fn from(value: char) -> Self {
-        match value {
+impl Pipe {
+    /// `None` if the character is not a pipe or the ground
+    fn from_char(c: char) -> Option<Self> {
+        Some(match c {
             '|' => Self::Vert,
             '-' => Self::Horz,
             'L' => Self::NE,
@@ -40,218 +58,73 @@ impl From<char> for Pipe {
             'F' => Self::SE,
             '.' => Self::None,
             'S' => Self::Start,
-            _ => unreachable!("invalid character"),
-        }
-    }
-}
-impl Pipe {
-    // fn directions(&self) -> Option<[Direction; 2]> {
-    //     use Direction::*;
-    //     match self {
-    //         Pipe::Vert => Some([North, South]),
-    //         Pipe::Horz => Some([East, West]),
-    //         Pipe::NE => Some([North, East]),
-    //         Pipe::NW => Some([North, West]),
-    //         Pipe::SE => Some([South, East]),
-    //         Pipe::SW => Some([South, West]),
-    //         Pipe::None => None,
-    //         Pipe::Start => None,
-    //     }
-    // }
-    fn connect(&self, direction: Direction) -> bool {
-        match (self, direction) {
-            (Self::Start, _)
-            | (Self::Vert | Self::NE | Self::NW, Direction::North)
-            | (Self::Vert | Self::SE | Self::SW, Direction::South)
-            | (Self::Horz | Self::NE | Self::SE, Direction::East)
-            | (Self::Horz | Self::NW | Self::SW, Direction::West) => true,
-            _ => false,
-        }
-    }
-    fn next_dir(&self, from_direction: Direction) -> Direction {
-        use Direction::*;
-        match (self, from_direction) {
-            (Self::Vert, South) | (Self::NE, East) | (Self::NW, West) => North,
-            (Self::Vert, North) | (Self::SE, East) | (Self::SW, West) => South,
-            (Self::Horz, West) | (Self::NE, North) | (Self::SE, South) => East,
-            (Self::Horz, East) | (Self::NW, North) | (Self::SW, South) => West,
-            _ => panic!(
-                "Attempted to enter a pipe from an invalid direction: {:?}, {:?}",
-                self, from_direction
-            ),
-        }
+            _ => return None,
+        })
     }
 }
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
+
+#[derive(Debug)]
+enum MazeError {
+    Grid(GridError),
+    NoStart,
 }
-impl Direction {
-    fn opposite(&self) -> Self {
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::North => Self::South,
-            Self::South => Self::North,
-            Self::East => Self::West,
-            Self::West => Self::East,
+            Self::Grid(_) => write!(f, "Invalid maze"),
+            Self::NoStart => write!(f, "The maze has no starting position 'S'"),
         }
     }
-    fn offset(&self, (x, y): (usize, usize)) -> (usize, usize) {
+}
+impl Error for MazeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
-            Self::North => (x, y - 1),
-            Self::South => (x, y + 1),
-            Self::East => (x + 1, y),
-            Self::West => (x - 1, y),
+            Self::Grid(e) => Some(e),
+            Self::NoStart => None,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct PipeInstance {
-    /// The end pos of the flow
-    pos: (usize, usize),
-    /// The direction of flow. The position the flow started was the offset position
-    from_direction: Direction,
-    pipe: Pipe,
+fn setup(input: &str) -> Result<Maze, MazeError> {
+    let grid = Grid::parse_with(input, Pipe::from_char).map_err(MazeError::Grid)?;
+    let start = grid
+        .position(|&pipe| pipe == Pipe::Start)
+        .ok_or(MazeError::NoStart)?;
+    Ok(Maze { grid, start })
 }
-impl PipeInstance {
-    fn new_from(
-        start_pos: (usize, usize),
-        to_direction: Direction,
-        pipe: Option<Pipe>,
-    ) -> Option<Self> {
-        let from_direction = to_direction.opposite();
-        pipe.map(|pipe| Self {
-            pos: to_direction.offset(start_pos),
-            from_direction,
-            pipe,
-        })
-    }
-    fn lookup(grid: &Grid, start_pos: (usize, usize), to_direction: Direction) -> Option<Self> {
-        use Direction::*;
-        match (start_pos, to_direction) {
-            ((0, _), West) | ((_, 0), North) => None,
-            ((x, _), East) if x >= grid.width - 1 => None,
-            ((_, y), East) if y >= grid.height - 1 => None,
-            _ => {
-                let pos = to_direction.offset(start_pos);
-                grid.map.get(&pos).and_then(|&pipe| {
-                    let from_direction = to_direction.opposite();
-                    if pipe.connect(from_direction) {
-                        Some(Self {
-                            pos,
-                            from_direction,
-                            pipe,
-                        })
-                    } else {
-                        None
-                    }
-                })
-            }
-        }
-    }
-    fn next_pipe(&self, grid: &Grid) -> Option<PipeInstance> {
-        Self::lookup(grid, self.pos, self.pipe.next_dir(self.from_direction))
+impl Pipe {
+    fn connect(&self, direction: Dir4) -> bool {
+        matches!(
+            (self, direction),
+            (Self::Start, _)
+                | (Self::Vert | Self::NE | Self::NW, Dir4::Up)
+                | (Self::Vert | Self::SE | Self::SW, Dir4::Down)
+                | (Self::Horz | Self::NE | Self::SE, Dir4::Right)
+                | (Self::Horz | Self::NW | Self::SW, Dir4::Left)
+        )
     }
 }
 
+crate::examples! {
+    "simple loop" => r"-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF" => (4, _),
+    "complex loop" => r"7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ" => (8, _),
+}
+
 crate::aoc! {
-    include_str!("../../../input/2023/10.txt"),
-//     r"
-// -L|F7
-// 7S-7|
-// L|7||
-// -L-J|
-// L|-JF",
-//     r"
-// 7-F7-
-// .FJ|7
-// SJLL7
-// |F--J
-// LJ.LJ",
-    |i| i.split("\n").filter(|s| s.len() > 0).enumerate().fold(Grid::default(), |mut grid, (y, s)| {
-        if y >= grid.height {
-            grid.height = y + 1;
-        }
-        s.chars().enumerate().for_each(|(x, c)| {
-            if x >= grid.width {
-                grid.width = x + 1;
-            }
-            match c {
-                'S' => grid.start = Some((x, y)),
-                '.' => (),
-                _ => {
-                    grid.map.insert((x, y), c.into());
-                }
-            }
-        });
-        grid
-    }),
-    |grid| {
-        use Direction::*;
-        let start_pos = dbg!(grid.start.unwrap());
-        let mut to_process = HashSet::new();
-        if let Some(p) = PipeInstance::lookup(grid, start_pos, North) {
-            to_process.insert(p);
-        }
-        if let Some(p) = PipeInstance::lookup(grid, start_pos, South) {
-            to_process.insert(p);
-        }
-        if let Some(p) = PipeInstance::lookup(grid, start_pos, East) {
-            to_process.insert(p);
-        }
-        if let Some(p) = PipeInstance::lookup(grid, start_pos, West) {
-            to_process.insert(p);
-        }
-        // if y > 0 {
-        //     if let Some(p) = grid.map.get(&(x, y - 1)) {
-        //         if p.connect(North) {
-        //             to_process.insert(PipeInstance::flow_from((x, y), North));
-        //         }
-        //     }
-        // }
-        // if y < grid.height - 1 {
-        //     if let Some(p) = grid.map.get(&(x, y + 1)) {
-        //         if p.connect(North) {
-        //             to_process.insert(PipeInstance::flow_from((x, y), South));
-        //         }
-        //     }
-        // }
-        // if x > 0 {
-        //     if let Some(p) = grid.map.get(&(x - 1, y)) {
-        //         if p.connect(East) {
-        //             to_process.insert(PipeInstance::flow_from((x, y), West));
-        //         }
-        //     }
-        // }
-        // if x < grid.width - 1 {
-        //     if let Some(p) = grid.map.get(&(x + 1, y)) {
-        //         if p.connect(West) {
-        //             to_process.insert(PipeInstance::flow_from((x, y), East));
-        //         }
-        //     }
-        // }
-        let mut computed = HashMap::new();
-        computed.insert(start_pos, 0);
-        let mut dist = 0;
-        while to_process.len() > 0 {
-            dist += 1;
-            // println!("Processing pipes at distance {dist} from ({start_pos:?}): {to_process:?}");//XXX
-            let mut new_pipes = HashSet::new();
-            for pipe in to_process.into_iter() {
-                // println!("Processing pipe: {pipe:?}");//XXX
-                computed.insert(pipe.pos, dist);
-                if let Some(p) = pipe.next_pipe(grid) {
-                    if !computed.contains_key(&p.pos) {
-                        new_pipes.insert(p);
-                    }
-                }
-            }
-            to_process = new_pipes;
-        }
-        dist
+    try_setup: setup,
+    part1: |maze| {
+        // The farthest point along the loop from the start
+        search::bfs_all([maze.start], |&pos| maze.connected(pos))
+            .into_values()
+            .max()
+            .unwrap_or(0)
     },
-    |grid| 0,
 }
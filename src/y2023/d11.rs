@@ -1,112 +1,111 @@
-use std::collections::HashSet;
+pub const TITLE: &str = "Cosmic Expansion";
 
 use itertools::Itertools;
 
-#[derive(Debug, Default)]
+use crate::{
+    geometry::Point,
+    grid::{Grid, GridError},
+    params::ParamError,
+    Param,
+};
+
+const EXPANSION: Param = Param::new(
+    "expansion",
+    "1000000",
+    "How many times larger each empty row and column is in part 2",
+);
+pub const PARAMS: &[Param] = &[EXPANSION];
+
+#[derive(Debug)]
 struct Universe {
-    width: usize,
-    height: usize,
-    empty_rows: Vec<usize>,
-    nonempty_cols: HashSet<usize>,
-    galaxies: Vec<(usize, usize)>,
+    empty_rows: Vec<bool>,
+    empty_cols: Vec<bool>,
+    galaxies: Vec<Point<usize>>,
 }
 impl Universe {
-    fn expanded_galaxies(&self, increase: usize) -> Vec<(usize, usize)> {
-        let col_increments = (0..self.width)
-            .fold(
-                (Vec::new(), 0usize),
-                |(mut vec, mut cumulative_inc), col| {
-                    if !self.nonempty_cols.contains(&col) {
-                        cumulative_inc += increase - 1;
-                    };
-                    vec.push(cumulative_inc);
-                    (vec, cumulative_inc)
-                },
-            )
-            .0;
-        let row_increments = (0..self.height)
-            .fold(
-                (Vec::new(), 0usize),
-                |(mut vec, mut cumulative_inc), col| {
-                    if self.empty_rows.contains(&col) {
-                        cumulative_inc += increase - 1;
-                    };
-                    vec.push(cumulative_inc);
-                    (vec, cumulative_inc)
-                },
-            )
-            .0;
+    fn expanded_galaxies(&self, increase: usize) -> Vec<Point<usize>> {
+        // The total increase before each row or column
+        let increments = |empty: &[bool]| {
+            empty
+                .iter()
+                .scan(0, |inc, &empty| {
+                    if empty {
+                        *inc += increase - 1;
+                    }
+                    Some(*inc)
+                })
+                .collect::<Vec<_>>()
+        };
+        let col_increments = increments(&self.empty_cols);
+        let row_increments = increments(&self.empty_rows);
         self.galaxies
             .iter()
-            .map(|(col, row)| (col + col_increments[*col], row + row_increments[*row]))
+            .map(|&p| Point::new(p.x + col_increments[p.x], p.y + row_increments[p.y]))
             .collect()
     }
+    /// The sum of the distances between each pair of galaxies
+    fn distances(&self, increase: usize) -> usize {
+        self.expanded_galaxies(increase)
+            .iter()
+            .combinations(2)
+            .fold(0, |sum, items| sum + items[0].manhattan(*items[1]))
+    }
+}
+
+const TEST_INPUT: &str = r"...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+crate::examples! {
+    "example" => TEST_INPUT => (374, _),
+    "expansion 10" => TEST_INPUT => (_, 1030) with { expansion = 10 },
+    "expansion 100" => TEST_INPUT => (_, 8410) with { expansion = 100 },
+}
+
+fn parse(input: &str) -> Result<Universe, GridError> {
+    let grid = Grid::parse_with(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    Ok(Universe {
+        empty_rows: grid.rows().map(|row| !row.contains(&true)).collect(),
+        empty_cols: grid
+            .columns()
+            .map(|mut col| !col.any(|&galaxy| galaxy))
+            .collect(),
+        galaxies: grid
+            .cells()
+            .filter(|&(_, &galaxy)| galaxy)
+            .map(|(pos, _)| pos)
+            .collect(),
+    })
 }
 
 crate::aoc! {
-    include_str!("../../../input/2023/11.txt"),
-//     r"
-// ...#......
-// .......#..
-// #.........
-// ..........
-// ......#...
-// .#........
-// .........#
-// ..........
-// .......#..
-// #...#.....",
-    |i| i.split("\n").filter(|s| s.len() > 0).fold((Universe::default(), 0), |(mut universe, mut row), s| {
-        if row >= universe.height {
-            universe.height = row + 1;
-        }
-        if s.len() > universe.width {
-            universe.width = s.len();
-        }
-        let mut empty = true;
-        for (col, c) in s.chars().enumerate() {
-            match c {
-                '#' => {
-                    universe.galaxies.push((col, row));
-                    universe.nonempty_cols.insert(col);
-                    empty = false;
-                },
-                _ => (),
+    solution: |params| {
+        use crate::dispatcher::*;
+        // Each empty row and column is replaced by `expansion` of them, so there must be at least one
+        let expansion = EXPANSION.get::<usize>(params).and_then(|e| {
+            if e >= 1 {
+                Ok(e)
+            } else {
+                Err(ParamError {
+                    name: EXPANSION.name,
+                    value: e.to_string(),
+                    reason: "must be at least 1".to_string(),
+                })
             }
-        }
-        if empty {
-            universe.empty_rows.push(row);
-        }
-        (universe, row + 1)
-    }).0,
-    |data| data.expanded_galaxies(2).iter().combinations(2).fold(0, |sum, items| {
-        let a = items[0];
-        let b = items[1];
-        let x = if a.0 > b.0 {
-            a.0 - b.0
-        } else {
-            b.0 - a.0
-        };
-        let y = if a.1 > b.1 {
-            a.1 - b.1
-        } else {
-            b.1 - a.1
-        };
-        sum + x + y
-    }),
-    |data| data.expanded_galaxies(1_000_000).iter().combinations(2).fold(0, |sum, items| {
-        let a = items[0];
-        let b = items[1];
-        let x = if a.0 > b.0 {
-            a.0 - b.0
-        } else {
-            b.0 - a.0
-        };
-        let y = if a.1 > b.1 {
-            a.1 - b.1
-        } else {
-            b.1 - a.1
-        };
-        sum + x + y
-    }),
+        });
+        DispatcherBuilder::try_setup(parse)
+            .part1(|universe| universe.distances(2))
+            .try_part2(move |universe| expansion.clone().map(|e| universe.distances(e)))
+    },
 }